@@ -1,5 +1,8 @@
 use std::{
-    path::{Path, PathBuf},
+    cell::RefCell,
+    collections::BTreeSet,
+    fmt,
+    path::{Component, Path, PathBuf},
     time::{Duration, SystemTime},
 };
 
@@ -11,13 +14,21 @@ use git2::{Commit, DiffOptions, Repository};
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// Lock file to analyze.
+    /// Lock file to analyze. Can be repeated to follow several files at once, eg: Kubernetes manifests
     #[arg(short, long)]
-    file: Option<String>,
+    file: Vec<String>,
 
     /// Name of the dependency to generate a timeline for
     #[arg(short, long)]
     dependency: String,
+
+    /// Format of the lock file. Detected from the file name or contents if not provided
+    #[arg(long, value_enum)]
+    format: Option<PackageManager>,
+
+    /// Fail on the first revision whose lock file can't be parsed, instead of skipping it
+    #[arg(long)]
+    strict: bool,
 }
 
 fn main() -> Result<()> {
@@ -27,39 +38,240 @@ fn main() -> Result<()> {
 
     let repo = Repository::open_from_env()?;
 
-    let file_path = if let Some(file) = &args.file {
-        PathBuf::from(file)
+    let file_paths = if args.file.is_empty() {
+        vec![detect_file()?]
     } else {
-        detect_file()?
+        args.file.iter().map(PathBuf::from).collect()
     };
 
-    let results = get_commits_for_file(&repo, &file_path)?
-        .into_iter()
-        // TODO we are ignoring some errors due to the flat_map
-        .flat_map(|commit| search_in_file(&repo, commit, &file_path, &args.dependency))
-        .collect::<Vec<_>>();
+    // files included by the lock files, eg: with `-r` in requirements files, are only known once
+    // they've been parsed. they're added to the commit walk so changes to them show up on the right
+    // date, and the history is searched again until no new ones turn up
+    let mut watched_paths = file_paths.clone();
+    let (results, failures) = loop {
+        let included_paths = RefCell::new(BTreeSet::new());
+
+        let mut results = Vec::new();
+        let mut failures = Vec::new();
+        'commits: for commit in get_commits_for_files(&repo, &watched_paths)? {
+            let id = commit.id();
+
+            let mut versions = BTreeSet::new();
+            for file_path in &file_paths {
+                let found = search_in_file(
+                    &repo,
+                    &commit,
+                    file_path,
+                    &args.dependency,
+                    args.format,
+                    &included_paths,
+                );
+
+                match found {
+                    // with several files, each version says which file it's in
+                    Ok(found) if file_paths.len() > 1 => {
+                        versions
+                            .extend(found.into_iter().map(|version| version.in_file(file_path)));
+                    }
+                    Ok(found) => versions.extend(found),
+                    Err(error) if args.strict => {
+                        return Err(error.wrap_err(format!(
+                            "Couldn't parse {} at commit {}",
+                            file_path.display(),
+                            id
+                        )));
+                    }
+                    Err(error) => {
+                        failures.push((id, file_path, error));
+                        continue 'commits;
+                    }
+                }
+            }
+
+            results.push(SearchResult {
+                versions,
+                date: date_from_commit(commit),
+            });
+        }
 
-    let mut previous_version = None;
+        let new_paths = included_paths
+            .into_inner()
+            .into_iter()
+            .filter(|path| !watched_paths.contains(path))
+            .collect::<Vec<_>>();
+        if new_paths.is_empty() {
+            break (results, failures);
+        }
+        watched_paths.extend(new_paths);
+    };
+
+    let mut previous_versions = BTreeSet::new();
     let iter = results.into_iter();
     for result in iter.rev() {
-        if result.version == previous_version {
+        if result.versions == previous_versions {
+            // versions are compared without their constraints, keep the latest ones to compare against
+            previous_versions = result.versions;
             continue;
         }
-        previous_version = result.version.clone();
 
         let date = DateTime::<Utc>::from(result.date);
-        println!(
-            "Version: {}, Date: {}",
-            result.version.unwrap_or_else(|| "None".to_string()),
-            date
+        let versions = if result.versions.is_empty() {
+            "None".to_string()
+        } else {
+            join(&result.versions)
+        };
+
+        let transition =
+            describe_transition(&args.dependency, &previous_versions, &result.versions);
+        if let Some(transition) = transition {
+            println!("Version: {}, Date: {} ({})", versions, date, transition);
+        } else {
+            println!("Version: {}, Date: {}", versions, date);
+        }
+
+        previous_versions = result.versions;
+    }
+
+    if !failures.is_empty() {
+        eprintln!(
+            "\nWarning: skipped {} commits where {} couldn't be parsed:",
+            failures.len(),
+            join(file_paths.iter().map(|path| path.display()))
         );
+        for (id, file_path, error) in failures {
+            // keep multi-line errors, like TOML's, indented under their commit
+            let reason = format!("{:#}", error).replace('\n', "\n    ");
+            if file_paths.len() > 1 {
+                eprintln!("  {} ({}): {}", id, file_path.display(), reason);
+            } else {
+                eprintln!("  {}: {}", id, reason);
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Describes what changed between two sets of versions, eg: `added syn 2.0.1 alongside 1.0.109`
+///
+/// Returns `None` for a plain change from one version to another, since that speaks for itself
+fn describe_transition(
+    dependency: &str,
+    previous: &BTreeSet<Version>,
+    current: &BTreeSet<Version>,
+) -> Option<String> {
+    let added = current.difference(previous).collect::<BTreeSet<_>>();
+    let removed = previous.difference(current).collect::<BTreeSet<_>>();
+    let kept = current.intersection(previous).collect::<BTreeSet<_>>();
+
+    if let (1, 1, Some(added), Some(removed)) =
+        (added.len(), removed.len(), added.first(), removed.first())
+    {
+        // same version, but rebuilt from a new recipe, eg: a Conan recipe revision
+        if added.version == removed.version && added.detail == removed.detail {
+            return Some(format!(
+                "new recipe revision of {dependency} {}: {} -> {}",
+                added.version,
+                removed.revision.as_deref().unwrap_or("none"),
+                added.revision.as_deref().unwrap_or("none"),
+            ));
+        }
+
+        // same version, but it's now used differently, eg: it moved from prod to dev dependencies
+        if added.version == removed.version {
+            return Some(format!(
+                "moved {dependency} {} from {} to {}",
+                added.version,
+                removed.detail.as_deref().unwrap_or("none"),
+                added.detail.as_deref().unwrap_or("none"),
+            ));
+        }
+    }
+
+    if previous.len() <= 1 && current.len() <= 1 {
+        return describe_constraints_change(previous.first()?, current.first()?);
+    }
+
+    let mut description = match (added.is_empty(), removed.is_empty()) {
+        (false, true) => format!("added {dependency} {}", join(&added)),
+        (true, false) => format!("removed {dependency} {}", join(&removed)),
+        _ => format!(
+            "replaced {dependency} {} with {}",
+            join(&removed),
+            join(&added)
+        ),
+    };
+
+    if !kept.is_empty() {
+        description.push_str(if removed.is_empty() {
+            " alongside "
+        } else {
+            ", keeping "
+        });
+        description.push_str(&join(&kept));
+    }
+
+    Some(description)
+}
+
+/// Describes a change of constraints that came with a change of version, eg: `constraints changed from ~> 5.0 to >= 5.0`
+fn describe_constraints_change(previous: &Version, current: &Version) -> Option<String> {
+    let previous = previous.constraints.as_deref();
+    let current = current.constraints.as_deref();
+
+    (previous != current).then(|| {
+        format!(
+            "constraints changed from {} to {}",
+            previous.unwrap_or("none"),
+            current.unwrap_or("none")
+        )
+    })
+}
+
+fn join<T: fmt::Display>(versions: impl IntoIterator<Item = T>) -> String {
+    versions
+        .into_iter()
+        .map(|version| version.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn detect_file() -> Result<PathBuf> {
-    let paths = ["Cargo.lock", "composer.lock", "package-lock.json"];
+    let paths = [
+        "Cargo.lock",
+        "composer.lock",
+        "package-lock.json",
+        "yarn.lock",
+        "pnpm-lock.yaml",
+        "bun.lock",
+        "bun.lockb",
+        "Gemfile.lock",
+        "poetry.lock",
+        "Pipfile.lock",
+        "uv.lock",
+        "pdm.lock",
+        "requirements.txt",
+        "go.mod",
+        "mix.lock",
+        "rebar.lock",
+        "pubspec.lock",
+        "Package.resolved",
+        "Podfile.lock",
+        "gradle.lockfile",
+        "buildscript-gradle.lockfile",
+        "packages.lock.json",
+        "paket.lock",
+        "flake.lock",
+        ".terraform.lock.hcl",
+        "deno.lock",
+        "Chart.lock",
+        "Manifest.toml",
+        "renv.lock",
+        "cabal.project.freeze",
+        "stack.yaml.lock",
+        "conan.lock",
+        "vcpkg.json",
+    ];
 
     for path in paths {
         let file = PathBuf::from(path);
@@ -73,7 +285,10 @@ fn detect_file() -> Result<PathBuf> {
     ))
 }
 
-fn get_commits_for_file<'a>(repo: &'a Repository, file_path: &Path) -> Result<Vec<Commit<'a>>> {
+fn get_commits_for_files<'a>(
+    repo: &'a Repository,
+    file_paths: &[PathBuf],
+) -> Result<Vec<Commit<'a>>> {
     let mut revwalk = repo.revwalk()?;
     revwalk.push_head()?;
     revwalk.set_sorting(git2::Sort::TIME)?;
@@ -91,7 +306,9 @@ fn get_commits_for_file<'a>(repo: &'a Repository, file_path: &Path) -> Result<Ve
         };
 
         let mut diff_opts = DiffOptions::new();
-        diff_opts.pathspec(file_path);
+        for file_path in file_paths {
+            diff_opts.pathspec(file_path);
+        }
         // we dont add `dependency` to the diff_opts, cause a version upgrade might not change a line that contains `dependency`
         let diff = repo.diff_tree_to_tree(Some(&parent_tree), Some(&tree), Some(&mut diff_opts))?;
 
@@ -105,42 +322,186 @@ fn get_commits_for_file<'a>(repo: &'a Repository, file_path: &Path) -> Result<Ve
 
 #[derive(Debug)]
 struct SearchResult {
-    version: Option<String>,
+    /// Every version of the dependency present in the lock file, empty if it's not there
+    versions: BTreeSet<Version>,
     date: SystemTime,
 }
 
+#[derive(Debug, Clone)]
+struct Version {
+    version: String,
+    /// Extra information about this copy of the dependency, eg: where it's installed
+    detail: Option<String>,
+    /// The range the version was picked from, eg: `~> 5.0`. It's left out when comparing versions,
+    /// so a change to it is only reported along with a change of version
+    constraints: Option<String>,
+    /// Revision of the recipe the version was built from, eg: Conan's `zlib/1.3#<revision>`.
+    /// Unlike constraints it's compared, so a new revision of the same version shows up on its own
+    revision: Option<String>,
+}
+
+impl Version {
+    fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            detail: None,
+            constraints: None,
+            revision: None,
+        }
+    }
+
+    fn with_detail(version: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            detail: Some(detail.into()),
+            constraints: None,
+            revision: None,
+        }
+    }
+
+    fn with_revision(self, revision: impl Into<String>) -> Self {
+        Self {
+            revision: Some(revision.into()),
+            ..self
+        }
+    }
+
+    fn with_constraints(self, constraints: impl Into<String>) -> Self {
+        Self {
+            constraints: Some(constraints.into()),
+            ..self
+        }
+    }
+
+    /// Adds the file this copy of the dependency is in to its detail
+    fn in_file(self, file_path: &Path) -> Self {
+        let detail = match self.detail {
+            Some(detail) => format!("{}, {}", file_path.display(), detail),
+            None => file_path.display().to_string(),
+        };
+
+        Self {
+            detail: Some(detail),
+            ..self
+        }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (&self.version, &self.detail, &self.revision).cmp(&(
+            &other.version,
+            &other.detail,
+            &other.revision,
+        ))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let revision = self
+            .revision
+            .as_ref()
+            .map(|revision| format!("revision {revision}"));
+        let notes = [self.detail.clone(), revision]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        if notes.is_empty() {
+            write!(f, "{}", self.version)
+        } else {
+            write!(f, "{} ({})", self.version, notes.join(", "))
+        }
+    }
+}
+
 fn search_in_file(
     repo: &Repository,
-    commit: Commit<'_>,
+    commit: &Commit<'_>,
     file_path: &Path,
     dependency: &str,
-) -> Result<SearchResult> {
-    let Ok(blob) = commit
-        .tree()?
-        .get_path(Path::new(file_path))?
-        .to_object(repo)?
-        .into_blob()
-    else {
+    format: Option<PackageManager>,
+    included_paths: &RefCell<BTreeSet<PathBuf>>,
+) -> Result<BTreeSet<Version>> {
+    let tree = commit.tree()?;
+    let entry = match tree.get_path(Path::new(file_path)) {
+        Ok(entry) => entry,
+        // the lock file was deleted in this commit
+        Err(error) if error.code() == git2::ErrorCode::NotFound => return Ok(BTreeSet::new()),
+        Err(error) => return Err(error.into()),
+    };
+
+    let Ok(blob) = entry.to_object(repo)?.into_blob() else {
         return Err(eyre!("Not a blob"));
     };
 
-    let content = String::from_utf8(blob.content().into())?;
+    let file_type = format
+        .or_else(|| {
+            file_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .and_then(PackageManager::guess_from_file_name)
+        })
+        .or_else(|| PackageManager::guess_from_content(blob.content()))
+        .ok_or_else(|| {
+            eyre!(
+                "Couldn't detect the format of {}, please specify one with the --format flag",
+                file_path.display()
+            )
+        })?;
 
-    let file_type = file_path
-        .file_name()
-        .and_then(|s| s.to_str())
-        .and_then(PackageManager::guess_from_file_name);
+    // some formats include other files, which are read from the same commit
+    let read_file = |path: &Path| -> Result<Vec<u8>> {
+        let path = normalize_path(&file_path.parent().unwrap_or(Path::new("")).join(path));
+        // recorded even if it can't be read, since it might exist in other commits
+        included_paths.borrow_mut().insert(path.clone());
+        let Ok(blob) = tree.get_path(&path)?.to_object(repo)?.into_blob() else {
+            return Err(eyre!("Not a blob"));
+        };
 
-    let version = if let Some(file_type) = file_type {
-        file_type.get_dependency_version(&content, dependency)?
-    } else {
-        todo!("Unimplemented: Can't automatically detect file type from file contents");
+        Ok(blob.content().to_vec())
     };
 
-    Ok(SearchResult {
-        version,
-        date: date_from_commit(commit),
-    })
+    file_type.get_dependency_version(blob.content(), dependency, &read_file)
+}
+
+/// Resolves `..` and `.` in a path relative to the root of the repository
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// Splits an npm style `name@version` into its name and version, eg: `@babel/core@7.0.0`
+fn split_package_spec(spec: &str) -> Option<(&str, &str)> {
+    // skip the first character so scoped packages keep their leading `@`
+    let index = spec.get(1..)?.find('@')? + 1;
+    Some((&spec[..index], &spec[index + 1..]))
 }
 
 fn date_from_commit(commit: Commit<'_>) -> SystemTime {
@@ -149,10 +510,46 @@ fn date_from_commit(commit: Commit<'_>) -> SystemTime {
     SystemTime::UNIX_EPOCH + Duration::from_secs(time.seconds() as u64)
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
 enum PackageManager {
     Composer,
     Cargo,
     Npm,
+    Yarn,
+    Pnpm,
+    Bun,
+    BunBinary,
+    Bundler,
+    Poetry,
+    Pipenv,
+    Uv,
+    Pdm,
+    Requirements,
+    GoMod,
+    GoSum,
+    Mix,
+    Rebar,
+    Pub,
+    #[value(name = "swiftpm")]
+    SwiftPm,
+    #[value(name = "cocoapods")]
+    CocoaPods,
+    Gradle,
+    Maven,
+    #[value(name = "nuget")]
+    NuGet,
+    Paket,
+    Nix,
+    Terraform,
+    Deno,
+    Helm,
+    Image,
+    Julia,
+    Renv,
+    Cabal,
+    Stack,
+    Conan,
+    Vcpkg,
 }
 
 impl PackageManager {
@@ -161,25 +558,248 @@ impl PackageManager {
             "composer.lock" => Some(PackageManager::Composer),
             "Cargo.lock" => Some(PackageManager::Cargo),
             "package-lock.json" => Some(PackageManager::Npm),
+            "yarn.lock" => Some(PackageManager::Yarn),
+            "pnpm-lock.yaml" => Some(PackageManager::Pnpm),
+            "bun.lock" => Some(PackageManager::Bun),
+            "bun.lockb" => Some(PackageManager::BunBinary),
+            "Gemfile.lock" => Some(PackageManager::Bundler),
+            "poetry.lock" => Some(PackageManager::Poetry),
+            "Pipfile.lock" => Some(PackageManager::Pipenv),
+            "uv.lock" => Some(PackageManager::Uv),
+            "pdm.lock" => Some(PackageManager::Pdm),
+            name if name.ends_with(".txt")
+                && (name.starts_with("requirements") || name.starts_with("constraints")) =>
+            {
+                Some(PackageManager::Requirements)
+            }
+            "go.mod" => Some(PackageManager::GoMod),
+            "go.sum" => Some(PackageManager::GoSum),
+            "mix.lock" => Some(PackageManager::Mix),
+            "rebar.lock" => Some(PackageManager::Rebar),
+            "pubspec.lock" => Some(PackageManager::Pub),
+            "Package.resolved" => Some(PackageManager::SwiftPm),
+            "Podfile.lock" => Some(PackageManager::CocoaPods),
+            "gradle.lockfile" | "buildscript-gradle.lockfile" => Some(PackageManager::Gradle),
+            "packages.lock.json" => Some(PackageManager::NuGet),
+            "paket.lock" => Some(PackageManager::Paket),
+            "flake.lock" => Some(PackageManager::Nix),
+            ".terraform.lock.hcl" => Some(PackageManager::Terraform),
+            "deno.lock" => Some(PackageManager::Deno),
+            "Chart.lock" => Some(PackageManager::Helm),
+            "Manifest.toml" | "JuliaManifest.toml" => Some(PackageManager::Julia),
+            "renv.lock" => Some(PackageManager::Renv),
+            "cabal.project.freeze" => Some(PackageManager::Cabal),
+            "stack.yaml.lock" => Some(PackageManager::Stack),
+            "conan.lock" => Some(PackageManager::Conan),
+            "vcpkg.json" => Some(PackageManager::Vcpkg),
             _ => None,
         }
     }
 
-    fn get_dependency_version(&self, content: &str, dependency: &str) -> Result<Option<String>> {
+    /// Recognizes the lock file format from its contents, for lock files with non-standard names
+    fn guess_from_content(content: &[u8]) -> Option<Self> {
+        if content.starts_with(bun::BINARY_HEADER) {
+            return Some(PackageManager::BunBinary);
+        }
+
+        let content = std::str::from_utf8(content).ok()?;
+        let has_line = |prefix: &str| content.lines().any(|line| line.starts_with(prefix));
+
+        // Poetry, uv and PDM also use `[[package]]`, so they have to be told apart from Cargo first
+        if has_line("[[package]]") {
+            return if has_line("python-versions") {
+                Some(PackageManager::Poetry)
+            } else if has_line("lock_version") {
+                Some(PackageManager::Pdm)
+            } else if has_line("requires-python") {
+                Some(PackageManager::Uv)
+            } else {
+                Some(PackageManager::Cargo)
+            };
+        }
+        if has_line("# yarn lockfile v1") || yarn_berry::is_berry_lock(content) {
+            return Some(PackageManager::Yarn);
+        }
+        if has_line("lockfileVersion:") {
+            return Some(PackageManager::Pnpm);
+        }
+
+        if has_line("  specs:") && (has_line("GEM") || has_line("GIT") || has_line("PATH")) {
+            return Some(PackageManager::Bundler);
+        }
+        if requirements::is_requirements_file(content) {
+            return Some(PackageManager::Requirements);
+        }
+
+        if has_line("module ") && (has_line("go ") || has_line("require")) {
+            return Some(PackageManager::GoMod);
+        }
+        if go::is_go_sum(content) {
+            return Some(PackageManager::GoSum);
+        }
+
+        let trimmed = content.trim_start();
+        if trimmed.starts_with("%{") {
+            return Some(PackageManager::Mix);
+        }
+        if trimmed.starts_with("[{<<") || (trimmed.starts_with("{\"") && trimmed.contains("<<\"")) {
+            return Some(PackageManager::Rebar);
+        }
+
+        if has_line("packages:") && has_line("sdks:") {
+            return Some(PackageManager::Pub);
+        }
+
+        if has_line("PODS:") {
+            return Some(PackageManager::CocoaPods);
+        }
+
+        if has_line("# This is a Gradle generated file for dependency locking.") {
+            return Some(PackageManager::Gradle);
+        }
+        if has_line("The following files have been resolved:") {
+            return Some(PackageManager::Maven);
+        }
+
+        if has_line("NUGET") && has_line("  remote:") {
+            return Some(PackageManager::Paket);
+        }
+
+        if has_line("provider \"") {
+            return Some(PackageManager::Terraform);
+        }
+
+        if has_line("dependencies:") && has_line("digest:") && has_line("generated:") {
+            return Some(PackageManager::Helm);
+        }
+        if content.lines().any(helm::is_image_line) {
+            return Some(PackageManager::Image);
+        }
+
+        if has_line("manifest_format") || has_line("[[deps.") {
+            return Some(PackageManager::Julia);
+        }
+        if has_line("constraints: ") && content.contains("any.") {
+            return Some(PackageManager::Cabal);
+        }
+        if has_line("packages:") && has_line("snapshots:") {
+            return Some(PackageManager::Stack);
+        }
+
+        // bun.lock allows trailing commas, so fall back to json5 if it's not valid JSON
+        let lock: serde_json::Value = serde_json::from_str(content)
+            .ok()
+            .or_else(|| json5::from_str(content).ok())?;
+
+        if lock.pointer("/_meta/pipfile-spec").is_some() {
+            Some(PackageManager::Pipenv)
+        } else if lock.get("content-hash").is_some() && lock.get("packages").is_some() {
+            Some(PackageManager::Composer)
+        } else if lock.get("lockfileVersion").is_some() && lock.get("workspaces").is_some() {
+            Some(PackageManager::Bun)
+        } else if lock.get("lockfileVersion").is_some() {
+            Some(PackageManager::Npm)
+        } else if lock.get("pins").is_some() || lock.pointer("/object/pins").is_some() {
+            Some(PackageManager::SwiftPm)
+        } else if lock.get("version").is_some()
+            && lock
+                .get("dependencies")
+                .is_some_and(serde_json::Value::is_object)
+        {
+            // vcpkg.json has `version` and `dependencies` too, but its dependencies are a list
+            Some(PackageManager::NuGet)
+        } else if lock.get("nodes").is_some() && lock.get("root").is_some() {
+            Some(PackageManager::Nix)
+        } else if lock
+            .get("version")
+            .is_some_and(serde_json::Value::is_string)
+            && ["remote", "specifiers", "packages", "npm", "jsr"]
+                .iter()
+                .any(|key| lock.get(key).is_some())
+        {
+            Some(PackageManager::Deno)
+        } else if lock.get("R").is_some() && lock.get("Packages").is_some() {
+            Some(PackageManager::Renv)
+        } else if lock.get("requires").is_some() && lock.get("build_requires").is_some() {
+            Some(PackageManager::Conan)
+        } else if lock.get("builtin-baseline").is_some() || lock.get("overrides").is_some() {
+            Some(PackageManager::Vcpkg)
+        } else {
+            None
+        }
+    }
+
+    /// `read_file` reads another file from the same commit, relative to the lock file
+    fn get_dependency_version(
+        &self,
+        content: &[u8],
+        dependency: &str,
+        read_file: &dyn Fn(&Path) -> Result<Vec<u8>>,
+    ) -> Result<BTreeSet<Version>> {
+        // binary formats are parsed from the raw bytes, the rest are text
+        let text = || std::str::from_utf8(content);
+
         match self {
-            PackageManager::Composer => composer::get_dependency_version(content, dependency),
-            PackageManager::Cargo => cargo::get_dependency_version(content, dependency),
-            PackageManager::Npm => npm::get_dependency_version(content, dependency),
+            PackageManager::Composer => composer::get_dependency_version(text()?, dependency),
+            PackageManager::Cargo => cargo::get_dependency_version(text()?, dependency),
+            PackageManager::Npm => npm::get_dependency_version(text()?, dependency),
+            PackageManager::Yarn => {
+                let content = text()?;
+                if yarn_berry::is_berry_lock(content) {
+                    yarn_berry::get_dependency_version(content, dependency)
+                } else {
+                    yarn::get_dependency_version(content, dependency)
+                }
+            }
+            PackageManager::Pnpm => pnpm::get_dependency_version(text()?, dependency),
+            PackageManager::Bun => bun::get_dependency_version(text()?, dependency),
+            PackageManager::BunBinary => bun::get_binary_dependency_version(content, dependency),
+            PackageManager::Bundler => bundler::get_dependency_version(text()?, dependency),
+            PackageManager::Poetry => python::get_dependency_version(text()?, dependency),
+            PackageManager::Pipenv => pipenv::get_dependency_version(text()?, dependency),
+            PackageManager::Uv => python::get_dependency_version(text()?, dependency),
+            PackageManager::Pdm => python::get_dependency_version(text()?, dependency),
+            PackageManager::Requirements => {
+                requirements::get_dependency_version(text()?, dependency, read_file)
+            }
+            PackageManager::GoMod => go::get_dependency_version(text()?, dependency, read_file),
+            PackageManager::GoSum => go::get_sum_dependency_version(text()?, dependency),
+            PackageManager::Mix => mix::get_dependency_version(text()?, dependency),
+            PackageManager::Rebar => mix::get_rebar_dependency_version(text()?, dependency),
+            PackageManager::Pub => pubspec::get_dependency_version(text()?, dependency),
+            PackageManager::SwiftPm => swift::get_dependency_version(text()?, dependency),
+            PackageManager::CocoaPods => cocoapods::get_dependency_version(text()?, dependency),
+            PackageManager::Gradle => gradle::get_dependency_version(text()?, dependency),
+            PackageManager::Maven => gradle::get_maven_dependency_version(text()?, dependency),
+            PackageManager::NuGet => nuget::get_dependency_version(text()?, dependency),
+            PackageManager::Paket => nuget::get_paket_dependency_version(text()?, dependency),
+            PackageManager::Nix => nix::get_dependency_version(text()?, dependency),
+            PackageManager::Terraform => terraform::get_dependency_version(text()?, dependency),
+            PackageManager::Deno => deno::get_dependency_version(text()?, dependency),
+            PackageManager::Helm => helm::get_dependency_version(text()?, dependency),
+            PackageManager::Image => helm::get_image_version(text()?, dependency),
+            PackageManager::Julia => julia::get_dependency_version(text()?, dependency),
+            PackageManager::Renv => renv::get_dependency_version(text()?, dependency),
+            PackageManager::Cabal => haskell::get_dependency_version(text()?, dependency),
+            PackageManager::Stack => haskell::get_stack_dependency_version(text()?, dependency),
+            PackageManager::Conan => conan::get_dependency_version(text()?, dependency),
+            PackageManager::Vcpkg => vcpkg::get_dependency_version(text()?, dependency),
         }
     }
 }
 
 mod composer {
+    use std::collections::BTreeSet;
+
     use color_eyre::eyre::Result;
 
+    use super::Version;
+
     #[derive(serde::Deserialize)]
     struct ComposerLock {
         packages: Vec<ComposerPackage>,
+        #[serde(rename = "packages-dev", default)]
+        packages_dev: Vec<ComposerPackage>,
     }
     #[derive(serde::Deserialize)]
     struct ComposerPackage {
@@ -189,25 +809,29 @@ mod composer {
         version: String,
     }
 
-    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<Option<String>> {
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
         let lock: ComposerLock = serde_json::from_str(content)?;
 
-        let package = lock
-            .packages
-            .iter()
-            .find(|package| package.name == dependency);
+        let prod = lock.packages.iter().map(|package| (package, "prod"));
+        let dev = lock.packages_dev.iter().map(|package| (package, "dev"));
 
-        if let Some(package) = package {
-            Ok(Some(package.version.clone()))
-        } else {
-            Ok(None)
-        }
+        let versions = prod
+            .chain(dev)
+            .filter(|(package, _)| package.name == dependency)
+            .map(|(package, list)| Version::with_detail(&package.version, list))
+            .collect();
+
+        Ok(versions)
     }
 }
 
 mod cargo {
+    use std::collections::BTreeSet;
+
     use color_eyre::eyre::Result;
 
+    use super::Version;
+
     #[derive(serde::Deserialize, Debug)]
     struct CargoLock {
         package: Vec<CargoPackage>,
@@ -219,47 +843,2621 @@ mod cargo {
         /// Package version
         version: String,
     }
-    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<Option<String>> {
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
         let lock: CargoLock = toml::from_str(content)?;
 
-        let package = lock
+        // there can be several versions of the same crate, eg: `syn` 1.x and 2.x
+        let versions = lock
             .package
             .iter()
-            .find(|package| package.name == dependency);
+            .filter(|package| package.name == dependency)
+            .map(|package| Version::new(&package.version))
+            .collect();
 
-        if let Some(package) = package {
-            Ok(Some(package.version.clone()))
-        } else {
-            Ok(None)
-        }
+        Ok(versions)
     }
 }
 
 mod npm {
-    use std::collections::HashMap;
+    use std::collections::{BTreeSet, HashMap};
 
     use color_eyre::eyre::Result;
 
+    use super::Version;
+
     #[derive(serde::Deserialize, Debug)]
     struct NpmLock {
-        packages: HashMap<String, NpmPackage>,
+        /// Only present since lockfileVersion 2
+        packages: Option<HashMap<String, NpmPackage>>,
+        /// Nested dependency tree used by lockfileVersion 1, kept in v2 for backwards compatibility
+        #[serde(default)]
+        dependencies: HashMap<String, NpmDependency>,
     }
     #[derive(serde::Deserialize, Debug)]
     struct NpmPackage {
         /// Package version
         version: Option<String>,
     }
+    #[derive(serde::Deserialize, Debug)]
+    struct NpmDependency {
+        /// Package version
+        version: Option<String>,
+        /// Copies of packages installed inside this one's `node_modules`
+        #[serde(default)]
+        dependencies: HashMap<String, NpmDependency>,
+    }
 
-    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<Option<String>> {
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
         let lock: NpmLock = serde_json::from_str(content)?;
 
-        if let Some(NpmPackage {
-            version: Some(version),
-        }) = lock.packages.get(&format!("node_modules/{dependency}"))
-        {
-            Ok(Some(version.clone()))
+        let mut versions = BTreeSet::new();
+        if let Some(packages) = &lock.packages {
+            let suffix = format!("node_modules/{dependency}");
+
+            for (path, package) in packages {
+                // matches both `node_modules/bar` and nested copies like `node_modules/foo/node_modules/bar`
+                let is_install = path == &suffix || path.ends_with(&format!("/{suffix}"));
+
+                if let (true, Some(version)) = (is_install, &package.version) {
+                    versions.insert(Version::with_detail(version, path));
+                }
+            }
+        } else {
+            collect_dependency_versions(&lock.dependencies, "", dependency, &mut versions);
+        }
+
+        Ok(versions)
+    }
+
+    /// Walks a lockfileVersion 1 `dependencies` tree, collecting every copy of `dependency`
+    /// along with the path it would be installed at
+    fn collect_dependency_versions(
+        dependencies: &HashMap<String, NpmDependency>,
+        parent_path: &str,
+        dependency: &str,
+        versions: &mut BTreeSet<Version>,
+    ) {
+        for (name, entry) in dependencies {
+            let path = format!("{parent_path}node_modules/{name}");
+
+            if let (true, Some(version)) = (name == dependency, &entry.version) {
+                versions.insert(Version::with_detail(version, &path));
+            }
+
+            collect_dependency_versions(
+                &entry.dependencies,
+                &format!("{path}/"),
+                dependency,
+                versions,
+            );
+        }
+    }
+}
+
+mod yarn {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::{split_package_spec, Version};
+
+    /// Parses the custom Yarn classic (v1) lock file format, where each entry is a list
+    /// of specifiers followed by its indented fields:
+    ///
+    /// ```text
+    /// "lodash@^4.17.0", "lodash@^4.17.21":
+    ///   version "4.17.21"
+    /// ```
+    ///
+    /// Several entries can resolve to different versions, so all of them are reported
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let mut versions = BTreeSet::new();
+        let mut in_entry = false;
+
+        for line in content.lines() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if !line.starts_with(' ') {
+                in_entry = line
+                    .trim_end()
+                    .trim_end_matches(':')
+                    .split(',')
+                    .any(|specifier| package_name(specifier) == dependency);
+                continue;
+            }
+
+            // only look at fields of the entry itself, not at nested `dependencies`
+            let Some(field) = line.strip_prefix("  ").filter(|f| !f.starts_with(' ')) else {
+                continue;
+            };
+
+            if in_entry {
+                if let Some(version) = field.strip_prefix("version ") {
+                    versions.insert(Version::new(version.trim().trim_matches('"')));
+                    in_entry = false;
+                }
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Strips the range from a specifier, eg: `"@babel/core@^7.0.0"` becomes `@babel/core`
+    pub fn package_name(specifier: &str) -> &str {
+        let specifier = specifier.trim().trim_matches('"');
+
+        split_package_spec(specifier).map_or(specifier, |(name, _)| name)
+    }
+}
+
+mod yarn_berry {
+    use std::collections::{BTreeSet, HashMap};
+
+    use color_eyre::eyre::Result;
+
+    use super::{yarn::package_name, Version};
+
+    #[derive(serde::Deserialize, Debug)]
+    struct BerryLock {
+        #[serde(rename = "__metadata")]
+        _metadata: serde::de::IgnoredAny,
+        #[serde(flatten)]
+        packages: HashMap<String, BerryPackage>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct BerryPackage {
+        /// Package version
+        version: String,
+        /// Locator the entry resolved to, eg: `react@npm:18.2.0`
+        resolution: Option<String>,
+    }
+
+    /// Yarn 2+ lock files are YAML and start with a `__metadata` block,
+    /// which Yarn classic lock files never have
+    pub fn is_berry_lock(content: &str) -> bool {
+        content.lines().any(|line| line.starts_with("__metadata:"))
+    }
+
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: BerryLock = serde_yaml::from_str(content)?;
+
+        let versions = lock
+            .packages
+            .iter()
+            .filter(|(key, package)| match &package.resolution {
+                Some(resolution) => package_name(resolution) == dependency,
+                None => key
+                    .split(',')
+                    .any(|locator| package_name(locator) == dependency),
+            })
+            .map(|(_, package)| Version::new(&package.version))
+            .collect();
+
+        Ok(versions)
+    }
+}
+
+mod pnpm {
+    use std::collections::{BTreeSet, HashMap};
+
+    use color_eyre::eyre::{eyre, Result};
+
+    use super::{split_package_spec, Version};
+
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct PnpmLock {
+        /// Either a number (`5.4`) or a string (`'6.0'`, `'9.0'`)
+        lockfile_version: serde_yaml::Value,
+        #[serde(default)]
+        packages: HashMap<String, PnpmPackage>,
+        /// Only present since v9, which moved the peer dependency variants out of `packages`
+        #[serde(default)]
+        snapshots: HashMap<String, serde::de::IgnoredAny>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct PnpmPackage {
+        /// Package name, only set for packages not coming from the registry
+        name: Option<String>,
+        /// Package version, only set for packages not coming from the registry
+        version: Option<String>,
+    }
+
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: PnpmLock = serde_yaml::from_str(content)?;
+
+        let major_version = match &lock.lockfile_version {
+            serde_yaml::Value::Number(number) => number.as_f64(),
+            serde_yaml::Value::String(string) => string.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| eyre!("Unknown pnpm lockfileVersion: {:?}", lock.lockfile_version))?
+            as u64;
+
+        let mut versions = BTreeSet::new();
+
+        for (key, package) in &lock.packages {
+            let (name, version) = match (&package.name, &package.version) {
+                (Some(name), Some(version)) => (name.as_str(), version.as_str()),
+                _ => match parse_key(key, major_version) {
+                    Some(parsed) => parsed,
+                    None => continue,
+                },
+            };
+
+            if name == dependency {
+                versions.insert(Version::new(version));
+            }
+        }
+        for key in lock.snapshots.keys() {
+            if let Some((name, version)) = parse_key(key, major_version) {
+                if name == dependency {
+                    versions.insert(Version::new(version));
+                }
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Splits a package key into its name and version. The key format depends on the lock file version:
+    ///
+    /// - v5: `/foo/1.2.3` and `/@scope/foo/1.2.3_react@18.2.0`
+    /// - v6: `/foo@1.2.3` and `/@scope/foo@1.2.3(react@18.2.0)`
+    /// - v9: `foo@1.2.3` and `@scope/foo@1.2.3(react@18.2.0)`
+    fn parse_key(key: &str, major_version: u64) -> Option<(&str, &str)> {
+        let key = key.strip_prefix('/').unwrap_or(key);
+
+        if major_version < 6 {
+            let (name, version) = key.rsplit_once('/')?;
+            let version = version.split('_').next()?;
+            Some((name, version))
         } else {
-            Ok(None)
+            split_package_spec(key.split('(').next()?)
+        }
+    }
+}
+
+mod bun {
+    use std::collections::{BTreeSet, HashMap};
+
+    use color_eyre::eyre::{eyre, Result};
+
+    use super::{split_package_spec, Version};
+
+    #[derive(serde::Deserialize, Debug)]
+    struct BunLock {
+        /// Maps an install path (`react`, `some-dep/react`) to an array
+        /// whose first element is the package identifier, eg: `react@18.2.0`
+        packages: HashMap<String, Vec<serde_json::Value>>,
+    }
+
+    /// Parses the text `bun.lock` format, which is JSONC (allows comments and trailing commas)
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: BunLock = json5::from_str(content)?;
+
+        let versions = lock
+            .packages
+            .values()
+            .filter_map(|package| package.first()?.as_str())
+            .filter_map(|identifier| {
+                let (name, version) = split_package_spec(identifier)?;
+                (name == dependency).then(|| Version::new(version))
+            })
+            .collect();
+
+        Ok(versions)
+    }
+
+    pub const BINARY_HEADER: &[u8] = b"#!/usr/bin/env bun\nbun-lockfile-format-v0\n";
+
+    /// Parses the binary `bun.lockb` format.
+    ///
+    /// Instead of decoding the package structs, whose layout changes between Bun versions,
+    /// this searches the string buffer for the tarball urls of the dependency,
+    /// eg: `https://registry.npmjs.org/@types/node/-/node-20.0.0.tgz`, and takes the version from those
+    pub fn get_binary_dependency_version(
+        content: &[u8],
+        dependency: &str,
+    ) -> Result<BTreeSet<Version>> {
+        if !content.starts_with(BINARY_HEADER) {
+            return Err(eyre!("Not a binary Bun lock file"));
+        }
+
+        let base_name = dependency.rsplit('/').next().unwrap_or(dependency);
+        let needle = format!("/{dependency}/-/{base_name}-");
+        let needle = needle.as_bytes();
+
+        let mut versions = BTreeSet::new();
+        let mut rest = content;
+        while let Some(index) = rest
+            .windows(needle.len())
+            .position(|window| window == needle)
+        {
+            // `/node/-/node-` also matches inside `/@types/node/-/node-`
+            let segment_start = rest[..index].iter().rposition(|byte| *byte == b'/');
+            let inside_scope =
+                segment_start.is_some_and(|start| rest.get(start + 1) == Some(&b'@'));
+
+            rest = &rest[index + needle.len()..];
+            if inside_scope {
+                continue;
+            }
+
+            let Some(end) = rest.windows(4).position(|window| window == b".tgz") else {
+                break;
+            };
+            let version = &rest[..end];
+            let is_version = version
+                .iter()
+                .all(|byte| byte.is_ascii_alphanumeric() || b".-+".contains(byte));
+
+            if !version.is_empty() && is_version {
+                versions.insert(Version::new(String::from_utf8_lossy(version)));
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+mod bundler {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    /// Parses `Gemfile.lock`, an indentation based format split into sections:
+    ///
+    /// ```text
+    /// GIT
+    ///   remote: https://github.com/rails/rails.git
+    ///   revision: 3b5d2a1...
+    ///   specs:
+    ///     rails (7.1.0.alpha)
+    ///
+    /// GEM
+    ///   remote: https://rubygems.org/
+    ///   specs:
+    ///     nokogiri (1.15.4-x86_64-linux)
+    ///       racc (~> 1.4)
+    ///
+    /// PLATFORMS
+    ///   x86_64-linux
+    /// ```
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let mut versions = BTreeSet::new();
+        let mut section = "";
+        let mut revision = None;
+
+        for line in content.lines() {
+            if !line.starts_with(' ') && !line.trim().is_empty() {
+                section = line.trim();
+                revision = None;
+                continue;
+            }
+
+            if !matches!(section, "GEM" | "GIT" | "PATH") {
+                continue;
+            }
+
+            if let Some(rev) = line.strip_prefix("  revision: ") {
+                revision = Some(rev.trim());
+                continue;
+            }
+
+            // specs are indented by four spaces, and their own dependencies by six
+            let Some(spec) = line.strip_prefix("    ").filter(|s| !s.starts_with(' ')) else {
+                continue;
+            };
+            let Some((name, version)) = spec.split_once(" (") else {
+                continue;
+            };
+            if name != dependency {
+                continue;
+            }
+
+            // platform specific builds, eg: `1.15.4-x86_64-linux`, are grouped under their version.
+            // RubyGems versions can't contain `-`, so everything after it is the platform
+            let version = version.trim_end_matches(')');
+            let version = version.split('-').next().unwrap_or(version);
+
+            versions.insert(match revision {
+                Some(revision) if section == "GIT" => {
+                    Version::with_detail(version, format!("git {:.7}", revision))
+                }
+                _ => Version::new(version),
+            });
+        }
+
+        Ok(versions)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn platform_builds_not_listed_in_platforms() {
+            let lock = "\
+GEM
+  remote: https://rubygems.org/
+  specs:
+    nokogiri (1.15.4)
+      racc (~> 1.4)
+    nokogiri (1.15.4-arm64-darwin)
+      racc (~> 1.4)
+    nokogiri (1.15.4-x86_64-linux)
+      racc (~> 1.4)
+
+PLATFORMS
+  arm64-darwin-22
+  ruby
+";
+
+            let versions = get_dependency_version(lock, "nokogiri").unwrap();
+            assert_eq!(versions, BTreeSet::from([Version::new("1.15.4")]));
+        }
+    }
+}
+
+mod python {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    /// Poetry, uv and PDM all lock packages in a TOML list of `[[package]]` tables
+    #[derive(serde::Deserialize, Debug)]
+    struct PythonLock {
+        #[serde(default)]
+        package: Vec<PythonPackage>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct PythonPackage {
+        /// Package name
+        name: String,
+        /// Package version, missing for some of uv's workspace members
+        version: Option<String>,
+    }
+
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: PythonLock = toml::from_str(content)?;
+        let dependency = normalize_name(dependency);
+
+        let versions = lock
+            .package
+            .iter()
+            .filter(|package| normalize_name(&package.name) == dependency)
+            .filter_map(|package| package.version.as_deref())
+            .map(Version::new)
+            .collect();
+
+        Ok(versions)
+    }
+
+    /// Normalizes a package name following PEP 503, so `Django`, `django` and `DJANGO` all match,
+    /// as do `zope.interface` and `zope_interface`
+    pub fn normalize_name(name: &str) -> String {
+        let mut normalized = String::with_capacity(name.len());
+
+        for c in name.chars() {
+            if matches!(c, '-' | '_' | '.') {
+                if !normalized.ends_with('-') {
+                    normalized.push('-');
+                }
+            } else {
+                normalized.push(c.to_ascii_lowercase());
+            }
+        }
+
+        normalized
+    }
+}
+
+mod pipenv {
+    use std::collections::{BTreeSet, HashMap};
+
+    use color_eyre::eyre::Result;
+
+    use super::{python::normalize_name, Version};
+
+    #[derive(serde::Deserialize, Debug)]
+    struct PipfileLock {
+        #[serde(default)]
+        default: HashMap<String, PipfilePackage>,
+        #[serde(default)]
+        develop: HashMap<String, PipfilePackage>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct PipfilePackage {
+        /// Package version pin, eg: `==4.2.1`. Missing for packages installed from git or a path
+        version: Option<String>,
+    }
+
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: PipfileLock = serde_json::from_str(content)?;
+        let dependency = normalize_name(dependency);
+
+        let default = lock.default.iter().map(|package| (package, "default"));
+        let develop = lock.develop.iter().map(|package| (package, "develop"));
+
+        let versions = default
+            .chain(develop)
+            .filter(|((name, _), _)| normalize_name(name) == dependency)
+            .filter_map(|((_, package), section)| {
+                let version = package.version.as_deref()?;
+                Some(Version::with_detail(
+                    version.trim_start_matches("=="),
+                    section,
+                ))
+            })
+            .collect();
+
+        Ok(versions)
+    }
+}
+
+mod requirements {
+    use std::{
+        collections::BTreeSet,
+        path::{Path, PathBuf},
+    };
+
+    use color_eyre::eyre::{Result, WrapErr};
+
+    use super::{python::normalize_name, Version};
+
+    /// Parses pinned requirements files, like the ones generated by `pip-compile`:
+    ///
+    /// ```text
+    /// -r base.txt
+    /// django[argon2]==4.2.1 ; python_version >= "3.8" \
+    ///     --hash=sha256:...
+    /// ```
+    ///
+    /// Files included with `-r` are read from the same commit, relative to the lock file
+    pub fn get_dependency_version(
+        content: &str,
+        dependency: &str,
+        read_file: &dyn Fn(&Path) -> Result<Vec<u8>>,
+    ) -> Result<BTreeSet<Version>> {
+        let mut versions = BTreeSet::new();
+        let mut visited = Vec::new();
+        collect_versions(
+            content,
+            Path::new(""),
+            &normalize_name(dependency),
+            read_file,
+            &mut visited,
+            &mut versions,
+        )?;
+
+        Ok(versions)
+    }
+
+    /// Whether every line of the file is a pin or an option, for requirements files with non-standard names
+    pub fn is_requirements_file(content: &str) -> bool {
+        let mut pins = 0;
+
+        for line in logical_lines(content) {
+            if line.is_empty() || line.starts_with('-') {
+                continue;
+            }
+            if parse_pin(&line).is_none() {
+                return false;
+            }
+            pins += 1;
+        }
+
+        pins > 0
+    }
+
+    fn collect_versions(
+        content: &str,
+        directory: &Path,
+        dependency: &str,
+        read_file: &dyn Fn(&Path) -> Result<Vec<u8>>,
+        visited: &mut Vec<PathBuf>,
+        versions: &mut BTreeSet<Version>,
+    ) -> Result<()> {
+        for line in logical_lines(content) {
+            let include = line
+                .strip_prefix("-r")
+                .or_else(|| line.strip_prefix("--requirement"))
+                .map(|path| path.trim_start_matches('=').trim());
+
+            if let Some(include) = include {
+                let path = directory.join(include);
+                if visited.contains(&path) {
+                    continue;
+                }
+                visited.push(path.clone());
+
+                let content = read_file(&path)
+                    .wrap_err_with(|| format!("Couldn't read included file {}", path.display()))?;
+                let content = std::str::from_utf8(&content)?;
+                let directory = path.parent().unwrap_or(Path::new(""));
+
+                collect_versions(content, directory, dependency, read_file, visited, versions)?;
+                continue;
+            }
+
+            let Some((name, version, marker)) = parse_pin(&line) else {
+                continue;
+            };
+
+            if normalize_name(name) == dependency {
+                versions.insert(match marker {
+                    Some(marker) => Version::with_detail(version, marker),
+                    None => Version::new(version),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Joins lines continued with a trailing `\` and strips comments
+    fn logical_lines(content: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut current = String::new();
+
+        for line in content.lines() {
+            // comments start with a `#` at the start of the line or after whitespace
+            let line = match line.find(" #").or_else(|| line.find("\t#")) {
+                Some(index) => &line[..index],
+                None if line.trim_start().starts_with('#') => "",
+                None => line,
+            };
+
+            if let Some(line) = line.strip_suffix('\\') {
+                current.push_str(line);
+                current.push(' ');
+                continue;
+            }
+
+            current.push_str(line);
+            lines.push(current.trim().to_string());
+            current.clear();
+        }
+        if !current.trim().is_empty() {
+            lines.push(current.trim().to_string());
+        }
+
+        lines
+    }
+
+    /// Parses a pinned requirement, eg: `django[argon2]==4.2.1 ; python_version >= "3.8" --hash=sha256:...`,
+    /// into its name, version and environment marker
+    fn parse_pin(line: &str) -> Option<(&str, &str, Option<&str>)> {
+        // per-requirement options like `--hash` always come last
+        let line = line.split(" --").next()?;
+        let (requirement, marker) = match line.split_once(';') {
+            Some((requirement, marker)) => (requirement, Some(marker.trim())),
+            None => (line, None),
+        };
+
+        let (name, version) = requirement.split_once("==")?;
+        let name = name.split('[').next()?.trim();
+        let version = version.trim_start_matches('=').trim();
+
+        let is_name = name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if name.is_empty()
+            || !is_name
+            || version.is_empty()
+            || version.contains(char::is_whitespace)
+        {
+            return None;
+        }
+
+        Some((name, version, marker.filter(|marker| !marker.is_empty())))
+    }
+}
+
+mod go {
+    use std::{collections::BTreeSet, path::Path};
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    struct Replace<'a> {
+        module: &'a str,
+        /// Only this version of `module` is replaced, or every version if `None`
+        version: Option<&'a str>,
+        new_module: &'a str,
+        /// `None` when replacing with a local path
+        new_version: Option<&'a str>,
+    }
+
+    /// Reads the `require` blocks and `replace` directives of a `go.mod`.
+    ///
+    /// Modules that are only needed indirectly might not be listed in older `go.mod` files,
+    /// so this falls back to the `go.sum` next to it. Reading it through `read_file` adds it to the
+    /// commit walk, so a bump that only touches `go.sum` still gets its own date
+    pub fn get_dependency_version(
+        content: &str,
+        dependency: &str,
+        read_file: &dyn Fn(&Path) -> Result<Vec<u8>>,
+    ) -> Result<BTreeSet<Version>> {
+        let mut requires = Vec::new();
+        let mut replaces = Vec::new();
+
+        let mut block = None;
+        for line in content.lines() {
+            let line = line.split("//").next().unwrap_or_default().trim();
+
+            let (directive, rest) = match block {
+                Some(_) if line == ")" => {
+                    block = None;
+                    continue;
+                }
+                Some(directive) => (directive, line),
+                None => {
+                    let Some((directive, rest)) = line.split_once(char::is_whitespace) else {
+                        continue;
+                    };
+                    if rest.trim() == "(" {
+                        block = Some(directive);
+                        continue;
+                    }
+                    (directive, rest.trim())
+                }
+            };
+
+            let fields = rest
+                .split_whitespace()
+                .map(|field| field.trim_matches('"'))
+                .collect::<Vec<_>>();
+            match (directive, fields.as_slice()) {
+                ("require", [module, version]) => requires.push((*module, *version)),
+                ("replace", [module, "=>", new_module, new_version @ ..])
+                | ("replace", [module, _, "=>", new_module, new_version @ ..]) => {
+                    replaces.push(Replace {
+                        module,
+                        version: (fields[1] != "=>").then_some(fields[1]),
+                        new_module,
+                        new_version: new_version.first().copied(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        let mut versions = BTreeSet::new();
+        for (module, version) in requires {
+            if module != dependency {
+                continue;
+            }
+
+            let replace = replaces.iter().find(|replace| {
+                replace.module == module && replace.version.is_none_or(|v| v == version)
+            });
+
+            versions.insert(match replace {
+                Some(Replace {
+                    new_module,
+                    new_version: Some(new_version),
+                    ..
+                }) => readable_version(new_version, Some(format!("replaced with {new_module}"))),
+                Some(Replace { new_module, .. }) => {
+                    Version::with_detail(*new_module, "replaced with local path")
+                }
+                None => readable_version(version, None),
+            });
+        }
+
+        if versions.is_empty() {
+            // go.sum is optional, so there's nothing to fall back to if it can't be read
+            if let Ok(go_sum) = read_file(Path::new("go.sum")) {
+                versions = sum_versions(std::str::from_utf8(&go_sum)?, dependency, Some("go.sum"));
+            }
+        }
+
+        Ok(versions)
+    }
+
+    pub fn get_sum_dependency_version(
+        content: &str,
+        dependency: &str,
+    ) -> Result<BTreeSet<Version>> {
+        Ok(sum_versions(content, dependency, None))
+    }
+
+    /// `go.sum` lines look like `github.com/pkg/errors v0.9.1 h1:...`
+    pub fn is_go_sum(content: &str) -> bool {
+        content.lines().all(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            matches!(fields.as_slice(), [_, _, hash] if hash.starts_with("h1:"))
+        }) && !content.trim().is_empty()
+    }
+
+    fn sum_versions(content: &str, dependency: &str, note: Option<&str>) -> BTreeSet<Version> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                Some((fields.next()?, fields.next()?))
+            })
+            // `v1.2.3/go.mod` lines are for modules whose go.mod was needed, but not their code
+            .filter(|(module, version)| *module == dependency && !version.ends_with("/go.mod"))
+            .map(|(_, version)| readable_version(version, note.map(str::to_string)))
+            .collect()
+    }
+
+    /// Splits pseudo-versions and `+incompatible` suffixes off a module version, so
+    /// `v0.0.0-20230101120000-abcdef123456` becomes `abcdef123456 (pseudo-version from 2023-01-01 12:00:00)`
+    fn readable_version(version: &str, note: Option<String>) -> Version {
+        let mut notes = Vec::from_iter(note);
+
+        let version = match version.strip_suffix("+incompatible") {
+            Some(version) => {
+                notes.push("incompatible".to_string());
+                version
+            }
+            None => version,
+        };
+
+        // pseudo-versions end in a timestamp and a 12 character commit hash, eg: `v1.2.4-0.20230101120000-abcdef123456`
+        let pseudo = version.rsplit_once('-').and_then(|(base, revision)| {
+            let timestamp = base.rsplit(['-', '.']).next()?;
+            let is_revision =
+                revision.len() == 12 && revision.chars().all(|c| c.is_ascii_hexdigit());
+            let is_timestamp =
+                timestamp.len() == 14 && timestamp.chars().all(|c| c.is_ascii_digit());
+
+            (is_revision && is_timestamp).then_some((revision, timestamp))
+        });
+
+        let version = match pseudo {
+            Some((revision, t)) => {
+                notes.insert(
+                    0,
+                    format!(
+                        "pseudo-version from {}-{}-{} {}:{}:{}",
+                        &t[0..4],
+                        &t[4..6],
+                        &t[6..8],
+                        &t[8..10],
+                        &t[10..12],
+                        &t[12..14]
+                    ),
+                );
+                revision
+            }
+            None => version,
+        };
+
+        if notes.is_empty() {
+            Version::new(version)
+        } else {
+            Version::with_detail(version, notes.join(", "))
+        }
+    }
+}
+
+mod mix {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::{eyre, Result};
+
+    use super::{term::Term, Version};
+
+    /// Parses `mix.lock`, an Elixir map from app name to the locked source:
+    ///
+    /// ```text
+    /// %{
+    ///   "phoenix": {:hex, :phoenix, "1.7.10", "0218...", [:mix], [...], "hexpm", "cf78..."},
+    ///   "my_dep": {:git, "https://github.com/me/my_dep.git", "8e1f...", [branch: "main"]},
+    /// }
+    /// ```
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let terms = super::term::parse(content)?;
+        let Some(Term::Map(entries)) = terms.first() else {
+            return Err(eyre!("Expected mix.lock to be a map"));
+        };
+
+        let mut versions = BTreeSet::new();
+        for (name, source) in entries {
+            if name.as_str() != Some(dependency) {
+                continue;
+            }
+
+            let Term::Tuple(source) = source else {
+                continue;
+            };
+            match source.as_slice() {
+                [kind, _, version, ..] if kind.as_str() == Some("hex") => {
+                    if let Some(version) = version.as_str() {
+                        versions.insert(Version::new(version));
+                    }
+                }
+                // git dependencies are locked to a commit
+                [kind, _, sha, options @ ..] if kind.as_str() == Some("git") => {
+                    if let Some(sha) = sha.as_str() {
+                        versions.insert(git_version(sha, options.first()));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Parses `rebar.lock`, which is a list of Erlang tuples, optionally wrapped in a tuple with the lock file version:
+    ///
+    /// ```text
+    /// {"1.2.0",
+    /// [{<<"cowboy">>,{pkg,<<"cowboy">>,<<"2.9.0">>},0},
+    ///  {<<"my_dep">>,{git,"https://github.com/me/my_dep.git",{ref,"8e1f..."}},0}]}.
+    /// ```
+    pub fn get_rebar_dependency_version(
+        content: &str,
+        dependency: &str,
+    ) -> Result<BTreeSet<Version>> {
+        let terms = super::term::parse(content)?;
+        let dependencies = match terms.first() {
+            Some(Term::Tuple(lock)) => match lock.as_slice() {
+                [_, Term::List(dependencies)] => dependencies,
+                _ => return Err(eyre!("Unknown rebar.lock format")),
+            },
+            Some(Term::List(dependencies)) => dependencies,
+            _ => return Err(eyre!("Unknown rebar.lock format")),
+        };
+
+        let mut versions = BTreeSet::new();
+        for entry in dependencies {
+            let Term::Tuple(entry) = entry else {
+                continue;
+            };
+            let [name, Term::Tuple(source), ..] = entry.as_slice() else {
+                continue;
+            };
+            if name.as_str() != Some(dependency) {
+                continue;
+            }
+
+            match source.as_slice() {
+                [kind, _, version, ..] if kind.as_str() == Some("pkg") => {
+                    if let Some(version) = version.as_str() {
+                        versions.insert(Version::new(version));
+                    }
+                }
+                [kind, _, Term::Tuple(reference)] if kind.as_str() == Some("git") => {
+                    if let [_, sha] = reference.as_slice() {
+                        if let Some(sha) = sha.as_str() {
+                            versions.insert(Version::with_detail(sha, "git"));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Git dependencies report their commit sha, along with the branch or tag they follow if any
+    fn git_version(sha: &str, options: Option<&Term>) -> Version {
+        let reference = match options {
+            Some(Term::List(options)) => options.iter().find_map(|option| match option {
+                Term::Tuple(pair) => match pair.as_slice() {
+                    [key, value] if matches!(key.as_str(), Some("branch" | "tag" | "ref")) => {
+                        Some(format!("{} {}", key.as_str()?, value.as_str()?))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }),
+            _ => None,
+        };
+
+        match reference {
+            Some(reference) => Version::with_detail(sha, format!("git, {reference}")),
+            None => Version::with_detail(sha, "git"),
+        }
+    }
+}
+
+mod term {
+    use color_eyre::eyre::{eyre, Result};
+
+    /// A value in Elixir or Erlang term syntax, as used by `mix.lock` and `rebar.lock`
+    #[derive(Debug)]
+    pub enum Term {
+        Atom(String),
+        /// Both strings and binaries, eg: `"phoenix"` and `<<"cowboy">>`
+        String(String),
+        /// Only used for dependency levels in `rebar.lock`, so its value isn't kept
+        Number,
+        Tuple(Vec<Term>),
+        /// Keyword lists like `[branch: "main"]` are lists of `{atom, value}` tuples, as in Elixir
+        List(Vec<Term>),
+        Map(Vec<(Term, Term)>),
+    }
+
+    impl Term {
+        /// The contents of atoms and strings, since the lock files use them interchangeably
+        pub fn as_str(&self) -> Option<&str> {
+            match self {
+                Term::Atom(value) | Term::String(value) => Some(value),
+                _ => None,
+            }
+        }
+    }
+
+    /// Parses every top level term, which are separated by `.` in Erlang files
+    pub fn parse(content: &str) -> Result<Vec<Term>> {
+        let mut parser = Parser {
+            input: content.as_bytes(),
+            position: 0,
+        };
+
+        let mut terms = Vec::new();
+        loop {
+            parser.skip_whitespace();
+            if parser.peek().is_none() {
+                return Ok(terms);
+            }
+
+            terms.push(parser.term()?);
+
+            parser.skip_whitespace();
+            if parser.peek() == Some(b'.') {
+                parser.position += 1;
+            }
+        }
+    }
+
+    struct Parser<'a> {
+        input: &'a [u8],
+        position: usize,
+    }
+
+    impl Parser<'_> {
+        fn peek(&self) -> Option<u8> {
+            self.input.get(self.position).copied()
+        }
+
+        fn rest(&self) -> &[u8] {
+            &self.input[self.position..]
+        }
+
+        fn error(&self, message: &str) -> color_eyre::eyre::Report {
+            eyre!("{} at byte {}", message, self.position)
+        }
+
+        fn expect(&mut self, expected: &[u8]) -> Result<()> {
+            self.skip_whitespace();
+            if !self.rest().starts_with(expected) {
+                return Err(
+                    self.error(&format!("Expected `{}`", String::from_utf8_lossy(expected)))
+                );
+            }
+            self.position += expected.len();
+            Ok(())
+        }
+
+        fn skip_whitespace(&mut self) {
+            while let Some(byte) = self.peek() {
+                if byte.is_ascii_whitespace() {
+                    self.position += 1;
+                } else if byte == b'%' && self.input.get(self.position + 1) != Some(&b'{') {
+                    // Erlang comment
+                    while self.peek().is_some_and(|byte| byte != b'\n') {
+                        self.position += 1;
+                    }
+                } else {
+                    break;
+                }
+            }
+        }
+
+        fn term(&mut self) -> Result<Term> {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b'%') => {
+                    self.expect(b"%{")?;
+                    let mut entries = Vec::new();
+                    for (key, value) in self.items(b'}', true)? {
+                        entries.push((key.ok_or_else(|| self.error("Expected map key"))?, value));
+                    }
+                    Ok(Term::Map(entries))
+                }
+                Some(b'{') => {
+                    self.position += 1;
+                    let items = self.items(b'}', false)?;
+                    Ok(Term::Tuple(
+                        items.into_iter().map(|(_, item)| item).collect(),
+                    ))
+                }
+                Some(b'[') => {
+                    self.position += 1;
+                    let items = self.items(b']', false)?;
+                    let items = items
+                        .into_iter()
+                        .map(|item| match item {
+                            (Some(key), value) => Term::Tuple(vec![key, value]),
+                            (None, value) => value,
+                        })
+                        .collect();
+                    Ok(Term::List(items))
+                }
+                Some(b'<') => {
+                    self.expect(b"<<")?;
+                    let value = self.string(b'"')?;
+                    self.expect(b">>")?;
+                    Ok(Term::String(value))
+                }
+                Some(b'"') => Ok(Term::String(self.string(b'"')?)),
+                Some(b'\'') => Ok(Term::Atom(self.string(b'\'')?)),
+                Some(b':') => {
+                    self.position += 1;
+                    if self.peek() == Some(b'"') {
+                        Ok(Term::Atom(self.string(b'"')?))
+                    } else {
+                        Ok(Term::Atom(self.identifier()))
+                    }
+                }
+                Some(byte) if byte.is_ascii_digit() || byte == b'-' => {
+                    self.position += 1;
+                    while self
+                        .peek()
+                        .is_some_and(|byte| byte.is_ascii_alphanumeric() || b"._".contains(&byte))
+                    {
+                        self.position += 1;
+                    }
+                    Ok(Term::Number)
+                }
+                Some(byte) if byte.is_ascii_alphabetic() || byte == b'_' => {
+                    Ok(Term::Atom(self.identifier()))
+                }
+                _ => Err(self.error("Unexpected character")),
+            }
+        }
+
+        /// Parses comma separated items up to `close`, with optional keys for maps (`"key": value`, `key => value`)
+        /// and keyword lists (`key: value`)
+        fn items(&mut self, close: u8, is_map: bool) -> Result<Vec<(Option<Term>, Term)>> {
+            let mut items = Vec::new();
+
+            loop {
+                self.skip_whitespace();
+                if self.peek() == Some(close) {
+                    self.position += 1;
+                    return Ok(items);
+                }
+
+                let term = self.term()?;
+                self.skip_whitespace();
+
+                let item = if self.rest().starts_with(b"=>") {
+                    self.position += 2;
+                    (Some(term), self.term()?)
+                } else if self.peek() == Some(b':') && (is_map || matches!(term, Term::Atom(_))) {
+                    self.position += 1;
+                    (Some(term), self.term()?)
+                } else {
+                    (None, term)
+                };
+                items.push(item);
+
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(b',') => self.position += 1,
+                    Some(byte) if byte == close => {}
+                    _ => return Err(self.error("Expected `,`")),
+                }
+            }
+        }
+
+        fn identifier(&mut self) -> String {
+            let start = self.position;
+            while self
+                .peek()
+                .is_some_and(|byte| byte.is_ascii_alphanumeric() || b"_@?!".contains(&byte))
+            {
+                self.position += 1;
+            }
+            String::from_utf8_lossy(&self.input[start..self.position]).into_owned()
+        }
+
+        fn string(&mut self, quote: u8) -> Result<String> {
+            self.expect(&[quote])?;
+
+            let mut value = Vec::new();
+            loop {
+                match self.peek() {
+                    Some(b'\\') => {
+                        value.extend(self.input.get(self.position + 1));
+                        self.position += 2;
+                    }
+                    Some(byte) if byte == quote => {
+                        self.position += 1;
+                        return Ok(String::from_utf8_lossy(&value).into_owned());
+                    }
+                    Some(byte) => {
+                        value.push(byte);
+                        self.position += 1;
+                    }
+                    None => return Err(self.error("Unterminated string")),
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::BTreeSet;
+
+        use super::*;
+        use crate::{mix, Version};
+
+        const MIX_LOCK: &str = r#"%{
+  "cowboy": {:hex, :cowboy, "2.10.0", "ff9ffeff91dae4ae270dd975642997afe2a1179d94b1887863e43f681a203e26", [:make, :rebar3], [{:cowlib, "2.12.1", [hex: :cowlib, repo: "hexpm", optional: false]}, {:ranch, "1.8.0", [hex: :ranch, repo: "hexpm", optional: false]}], "hexpm", "3afdccb7183cc6f143cb14d3cf51fa00e53db9ec80cdcd525482f5e99bc41d6b"},
+  "my_dep": {:git, "https://github.com/me/my_dep.git", "8e1f3e6c7d0a3b4b2a1f0e9d8c7b6a5f4e3d2c1b", [branch: "main"]},
+  "phoenix": {:hex, :phoenix, "1.7.10", "02189140a61b2ce85bb633a9b6fd02dff705a5f1596869547aeb2b2b95edd729", [:mix], [{:castore, ">= 0.0.0", [hex: :castore, repo: "hexpm", optional: false]}, {:jason, "~> 1.0", [hex: :jason, repo: "hexpm", optional: true]}], "hexpm", "cf784932e010fd736d656d7fead6a584a4498efefe5b8227e9f383bf15bb79d0"},
+}
+"#;
+
+        const REBAR_LOCK: &str = r#"{"1.2.0",
+[{<<"cowboy">>,{pkg,<<"cowboy">>,<<"2.9.0">>},0},
+ {<<"cowlib">>,{pkg,<<"cowlib">>,<<"2.11.0">>},1},
+ {<<"my_dep">>,
+  {git,"https://github.com/me/my_dep.git",
+       {ref,"8e1f3e6c7d0a3b4b2a1f0e9d8c7b6a5f4e3d2c1b"}},
+  0}]}.
+[
+{pkg_hash,[
+ {<<"cowboy">>, <<"2A3AFDCB2C6A4D5E6F708192A3B4C5D6E7F8091A2B3C4D5E6F708192A3B4C5D6">>},
+ {<<"cowlib">>, <<"9AE4DE0F4A6B0E5C8E1F2A3B4C5D6E7F8091A2B3C4D5E6F708192A3B4C5D6E7">>}]},
+{pkg_hash_ext,[
+ {<<"cowboy">>, <<"04FD8C6A2B1E0F9D8C7B6A5F4E3D2C1B0A9F8E7D6C5B4A3F2E1D0C9B8A7F6E5D">>},
+ {<<"cowlib">>, <<"E4175DC240A70D996156160891E1C62238EDE1729E45740BDD38064DAD476170">>}]}
+].
+"#;
+
+        #[test]
+        fn mix_lock() {
+            let terms = parse(MIX_LOCK).unwrap();
+            let [Term::Map(entries)] = terms.as_slice() else {
+                panic!("expected a single map, got {terms:?}");
+            };
+            assert_eq!(entries.len(), 3);
+
+            // keyword lists nested in lists in tuples become `{key, value}` tuples
+            let (_, Term::Tuple(cowboy)) = &entries[0] else {
+                panic!("expected a tuple");
+            };
+            let Term::List(dependencies) = &cowboy[5] else {
+                panic!("expected a list of dependencies");
+            };
+            let Term::Tuple(cowlib) = &dependencies[0] else {
+                panic!("expected a tuple");
+            };
+            let Term::List(options) = &cowlib[2] else {
+                panic!("expected a keyword list");
+            };
+            let Term::Tuple(repo) = &options[1] else {
+                panic!("expected a keyword pair");
+            };
+            assert_eq!(repo[0].as_str(), Some("repo"));
+            assert_eq!(repo[1].as_str(), Some("hexpm"));
+
+            assert_eq!(
+                mix::get_dependency_version(MIX_LOCK, "phoenix").unwrap(),
+                BTreeSet::from([Version::new("1.7.10")])
+            );
+            assert_eq!(
+                mix::get_dependency_version(MIX_LOCK, "my_dep").unwrap(),
+                BTreeSet::from([Version::with_detail(
+                    "8e1f3e6c7d0a3b4b2a1f0e9d8c7b6a5f4e3d2c1b",
+                    "git, branch main"
+                )])
+            );
+        }
+
+        #[test]
+        fn rebar_lock() {
+            let terms = parse(REBAR_LOCK).unwrap();
+            // the versioned lock, then the `pkg_hash` list
+            let [Term::Tuple(lock), Term::List(hashes)] = terms.as_slice() else {
+                panic!("expected two terms, got {terms:?}");
+            };
+            assert_eq!(lock[0].as_str(), Some("1.2.0"));
+            let Term::Tuple(pkg_hash) = &hashes[0] else {
+                panic!("expected a tuple");
+            };
+            assert_eq!(pkg_hash[0].as_str(), Some("pkg_hash"));
+
+            assert_eq!(
+                mix::get_rebar_dependency_version(REBAR_LOCK, "cowlib").unwrap(),
+                BTreeSet::from([Version::new("2.11.0")])
+            );
+            assert_eq!(
+                mix::get_rebar_dependency_version(REBAR_LOCK, "my_dep").unwrap(),
+                BTreeSet::from([Version::with_detail(
+                    "8e1f3e6c7d0a3b4b2a1f0e9d8c7b6a5f4e3d2c1b",
+                    "git"
+                )])
+            );
+        }
+
+        #[test]
+        fn unterminated_string() {
+            let error = parse(r#"%{"phoenix": {:hex, :phoenix, "1.7.10}}"#).unwrap_err();
+            assert!(error.to_string().contains("Unterminated string"), "{error}");
+        }
+
+        #[test]
+        fn missing_comma() {
+            let error = parse(r#"[{<<"cowboy">>,{pkg,<<"cowboy">>,<<"2.9.0">>} 0}]."#).unwrap_err();
+            assert_eq!(error.to_string(), "Expected `,` at byte 46");
+        }
+    }
+}
+
+mod pubspec {
+    use std::collections::{BTreeSet, HashMap};
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    struct PubspecLock {
+        #[serde(default)]
+        packages: HashMap<String, PubPackage>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct PubPackage {
+        /// Package version
+        version: String,
+        /// How the package is depended on: `direct main`, `direct dev`, `direct overridden` or `transitive`
+        dependency: Option<String>,
+    }
+
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: PubspecLock = serde_yaml::from_str(content)?;
+
+        let versions = lock
+            .packages
+            .get(dependency)
+            .map(|package| match &package.dependency {
+                Some(kind) => Version::with_detail(&package.version, kind),
+                None => Version::new(&package.version),
+            })
+            .into_iter()
+            .collect();
+
+        Ok(versions)
+    }
+}
+
+mod swift {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    struct PackageResolved {
+        /// Only in v1
+        object: Option<PackageResolvedObject>,
+        /// Only since v2
+        #[serde(default)]
+        pins: Vec<Pin>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct PackageResolvedObject {
+        pins: Vec<Pin>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct Pin {
+        /// Package name in v1, eg: `Alamofire`
+        package: Option<String>,
+        /// Lowercase package name since v2, eg: `alamofire`
+        identity: Option<String>,
+        /// Repository url in v1
+        #[serde(rename = "repositoryURL")]
+        repository_url: Option<String>,
+        /// Repository url since v2
+        location: Option<String>,
+        state: PinState,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct PinState {
+        version: Option<String>,
+        branch: Option<String>,
+        revision: Option<String>,
+    }
+
+    /// Parses every schema version of `Package.resolved`. v1 nests the pins in `object.pins`,
+    /// while v2 and v3 have them at the top level
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let resolved: PackageResolved = serde_json::from_str(content)?;
+
+        let pins = resolved
+            .object
+            .map(|object| object.pins)
+            .unwrap_or_default()
+            .into_iter()
+            .chain(resolved.pins);
+
+        let mut versions = BTreeSet::new();
+        for pin in pins {
+            let url = pin.repository_url.or(pin.location);
+            let names = [
+                pin.package,
+                pin.identity,
+                url.as_deref().map(repository_name),
+            ];
+            let matches = names
+                .iter()
+                .flatten()
+                .any(|name| name.eq_ignore_ascii_case(dependency))
+                || url.as_deref() == Some(dependency);
+            if !matches {
+                continue;
+            }
+
+            // pins that follow a branch or revision have no version, so report the revision
+            let state = pin.state;
+            let version = match (state.version, state.revision) {
+                (Some(version), _) => Version::new(version),
+                (None, Some(revision)) => match state.branch {
+                    Some(branch) => Version::with_detail(revision, format!("branch {branch}")),
+                    None => Version::with_detail(revision, "revision"),
+                },
+                (None, None) => continue,
+            };
+            versions.insert(version);
+        }
+
+        Ok(versions)
+    }
+
+    /// `https://github.com/Alamofire/Alamofire.git` becomes `Alamofire`
+    fn repository_name(url: &str) -> String {
+        let name = url.trim_end_matches('/').rsplit('/').next().unwrap_or(url);
+        name.trim_end_matches(".git").to_string()
+    }
+}
+
+mod cocoapods {
+    use std::collections::{BTreeSet, HashMap};
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    struct PodfileLock {
+        #[serde(rename = "PODS", default)]
+        pods: Vec<Pod>,
+        #[serde(rename = "CHECKOUT OPTIONS", default)]
+        checkout_options: HashMap<String, HashMap<String, String>>,
+    }
+    /// Either `Alamofire (5.6.4)`, or a map from it to its dependencies
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(untagged)]
+    enum Pod {
+        Name(String),
+        WithDependencies(HashMap<String, serde_yaml::Value>),
+    }
+
+    /// Parses the `PODS:` list of `Podfile.lock`:
+    ///
+    /// ```text
+    /// PODS:
+    ///   - Alamofire (5.6.4)
+    ///   - Firebase/Core (10.0.0):
+    ///     - FirebaseCore (= 10.0.0)
+    /// ```
+    ///
+    /// Subspecs like `Firebase/Core` also count as their root pod, `Firebase`
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: PodfileLock = serde_yaml::from_str(content)?;
+
+        let names = lock.pods.iter().flat_map(|pod| match pod {
+            Pod::Name(name) => vec![name.as_str()],
+            Pod::WithDependencies(pod) => pod.keys().map(String::as_str).collect(),
+        });
+
+        let mut versions = BTreeSet::new();
+        for pod in names {
+            let Some((name, version)) = pod.split_once(" (") else {
+                continue;
+            };
+            let root = name.split('/').next().unwrap_or(name);
+            if name != dependency && root != dependency {
+                continue;
+            }
+
+            let version = version.trim_end_matches(')');
+            // pods checked out from git are pinned to a commit
+            let commit = lock
+                .checkout_options
+                .get(root)
+                .and_then(|options| options.get(":commit"));
+
+            versions.insert(match commit {
+                Some(commit) => Version::with_detail(version, format!("commit {commit}")),
+                None => Version::new(version),
+            });
+        }
+
+        Ok(versions)
+    }
+}
+
+mod gradle {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    /// Parses Gradle dependency lock files, whose lines look like
+    /// `com.google.guava:guava:31.1-jre=compileClasspath,runtimeClasspath`.
+    ///
+    /// Dependencies are addressed as `group:artifact`, and reported along with the configurations they're in
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let mut versions = BTreeSet::new();
+
+        for line in content.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((coordinates, configurations)) = line.trim().split_once('=') else {
+                continue;
+            };
+            let Some((module, version)) = coordinates.rsplit_once(':') else {
+                continue;
+            };
+
+            if module == dependency {
+                let configurations = configurations.split(',').collect::<Vec<_>>();
+                versions.insert(Version::with_detail(version, configurations.join(", ")));
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Parses the resolved dependencies written by Maven's `mvn dependency:list -DoutputFile=...`:
+    ///
+    /// ```text
+    /// The following files have been resolved:
+    ///    com.google.guava:guava:jar:31.1-jre:compile
+    ///    org.junit.jupiter:junit-jupiter:jar:5.10.0:test -- module org.junit.jupiter
+    /// ```
+    ///
+    /// Dependencies are addressed as `group:artifact`, and reported along with their scope
+    pub fn get_maven_dependency_version(
+        content: &str,
+        dependency: &str,
+    ) -> Result<BTreeSet<Version>> {
+        let mut versions = BTreeSet::new();
+
+        for line in content.lines() {
+            // drop suffixes like ` -- module ...` and ` (optional)`
+            let Some(coordinates) = line.split_whitespace().next() else {
+                continue;
+            };
+
+            // `group:artifact:type:version:scope`, with an optional classifier before the version
+            let parts = coordinates.split(':').collect::<Vec<_>>();
+            let (version, scope) = match parts.as_slice() {
+                [_, _, _, version, scope] | [_, _, _, _, version, scope] => (version, scope),
+                _ => continue,
+            };
+
+            if format!("{}:{}", parts[0], parts[1]) == dependency {
+                versions.insert(Version::with_detail(*version, *scope));
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+mod nuget {
+    use std::collections::{BTreeSet, HashMap};
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    struct NuGetLock {
+        /// Packages for each target framework, eg: `net6.0` or `net8.0/win-x64`
+        dependencies: HashMap<String, HashMap<String, NuGetPackage>>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct NuGetPackage {
+        /// `Direct`, `Transitive`, `CentralTransitive` or `Project`
+        #[serde(rename = "type")]
+        kind: String,
+        /// Package version. Project references don't have one
+        resolved: Option<String>,
+    }
+
+    /// Parses `packages.lock.json`. The same package can resolve differently under each
+    /// target framework, so every version is reported along with its framework
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: NuGetLock = serde_json::from_str(content)?;
+
+        let mut versions = BTreeSet::new();
+        for (framework, packages) in &lock.dependencies {
+            for (name, package) in packages {
+                // package ids are case insensitive
+                if !name.eq_ignore_ascii_case(dependency) {
+                    continue;
+                }
+
+                if let Some(version) = &package.resolved {
+                    let detail = format!("{framework}, {}", package.kind.to_lowercase());
+                    versions.insert(Version::with_detail(version, detail));
+                }
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Parses `paket.lock`, whose packages are listed under `NUGET` sections:
+    ///
+    /// ```text
+    /// NUGET
+    ///   remote: https://api.nuget.org/v3/index.json
+    ///     Newtonsoft.Json (13.0.1) - restriction: >= net6.0
+    ///       System.Runtime (>= 4.3)
+    /// GROUP Build
+    /// NUGET
+    ///   remote: https://api.nuget.org/v3/index.json
+    ///     FAKE (5.20.4)
+    /// ```
+    ///
+    /// Packages outside the main group are reported along with their group
+    pub fn get_paket_dependency_version(
+        content: &str,
+        dependency: &str,
+    ) -> Result<BTreeSet<Version>> {
+        let mut versions = BTreeSet::new();
+        let mut group = None;
+        let mut section = "";
+
+        for line in content.lines() {
+            if !line.starts_with(' ') {
+                match line.trim().strip_prefix("GROUP ") {
+                    Some(name) => group = Some(name.trim()).filter(|name| *name != "Main"),
+                    None => section = line.trim(),
+                }
+                continue;
+            }
+
+            if section != "NUGET" {
+                continue;
+            }
+
+            // packages are indented by four spaces, and their own dependencies by six
+            let Some(package) = line.strip_prefix("    ").filter(|p| !p.starts_with(' ')) else {
+                continue;
+            };
+            let Some((name, rest)) = package.split_once(" (") else {
+                continue;
+            };
+            let Some((version, _)) = rest.split_once(')') else {
+                continue;
+            };
+
+            if name.eq_ignore_ascii_case(dependency) {
+                versions.insert(match group {
+                    Some(group) => Version::with_detail(version, format!("group {group}")),
+                    None => Version::new(version),
+                });
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+mod nix {
+    use std::collections::{BTreeSet, HashMap};
+
+    use chrono::DateTime;
+    use color_eyre::eyre::{eyre, Result};
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    struct FlakeLock {
+        nodes: HashMap<String, Node>,
+        /// Name of the node for the flake itself
+        root: String,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct Node {
+        #[serde(default)]
+        inputs: HashMap<String, Input>,
+        locked: Option<Locked>,
+        original: Option<Original>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(untagged)]
+    enum Input {
+        /// Name of the node the input is locked to
+        Node(String),
+        /// Path of inputs, starting from the root, that this input `follows`, eg: `["home-manager", "nixpkgs"]`
+        Follows(Vec<String>),
+    }
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct Locked {
+        rev: Option<String>,
+        nar_hash: Option<String>,
+        /// Unix timestamp of the locked revision
+        last_modified: Option<i64>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct Original {
+        #[serde(rename = "ref")]
+        reference: Option<String>,
+    }
+
+    /// Reports the revision an input of the flake is locked to, along with the ref it follows
+    /// (eg: `nixos-23.11`) and when that revision was made.
+    ///
+    /// Inputs of inputs can be addressed with a path, eg: `home-manager/nixpkgs`
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: FlakeLock = serde_json::from_str(content)?;
+
+        let path = dependency
+            .split('/')
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let Some((name, follows)) = resolve(&lock, &path, 0)? else {
+            return Ok(BTreeSet::new());
+        };
+
+        let node = lock
+            .nodes
+            .get(name)
+            .ok_or_else(|| eyre!("Input {} points to a missing node {}", dependency, name))?;
+        let Some(locked) = &node.locked else {
+            return Ok(BTreeSet::new());
+        };
+
+        let mut details = Vec::new();
+        if let Some(reference) = node
+            .original
+            .as_ref()
+            .and_then(|original| original.reference.as_ref())
+        {
+            details.push(reference.clone());
+        }
+        if let Some(date) = locked
+            .last_modified
+            .and_then(|time| DateTime::from_timestamp(time, 0))
+        {
+            details.push(format!("modified {}", date.format("%Y-%m-%d")));
+        }
+        if let Some(follows) = follows {
+            details.push(format!("follows {follows}"));
+        }
+
+        let Some(revision) = locked.rev.as_ref().or(locked.nar_hash.as_ref()) else {
+            return Ok(BTreeSet::new());
+        };
+        let version = if details.is_empty() {
+            Version::new(revision)
+        } else {
+            Version::with_detail(revision, details.join(", "))
+        };
+
+        Ok(BTreeSet::from([version]))
+    }
+
+    /// Walks a path of inputs from the root node, returning the name of the node it ends up at,
+    /// and the input path it followed to get there, if any
+    fn resolve<'a>(
+        lock: &'a FlakeLock,
+        path: &[String],
+        depth: usize,
+    ) -> Result<Option<(&'a str, Option<String>)>> {
+        // `follows` can't form cycles in valid lock files, but don't loop forever on invalid ones
+        if depth > 32 {
+            return Err(eyre!(
+                "Too many levels of `follows` resolving {}",
+                path.join("/")
+            ));
+        }
+
+        let mut node = lock.root.as_str();
+        let mut followed = None;
+
+        for segment in path {
+            let Some(input) = lock
+                .nodes
+                .get(node)
+                .and_then(|node| node.inputs.get(segment))
+            else {
+                return Ok(None);
+            };
+
+            node = match input {
+                Input::Node(name) => name,
+                Input::Follows(follows) => {
+                    let Some((name, _)) = resolve(lock, follows, depth + 1)? else {
+                        return Ok(None);
+                    };
+                    followed = Some(follows.join("/"));
+                    name
+                }
+            };
+        }
+
+        Ok(Some((node, followed)))
+    }
+}
+
+mod terraform {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    /// Parses `.terraform.lock.hcl`, which has a block for each provider:
+    ///
+    /// ```text
+    /// provider "registry.terraform.io/hashicorp/aws" {
+    ///   version     = "5.31.0"
+    ///   constraints = "~> 5.0"
+    ///   hashes = [...]
+    /// }
+    /// ```
+    ///
+    /// Providers can be addressed either by their full address or as `hashicorp/aws`.
+    /// Constraints are kept with the version, so changes to them are reported when the version changes
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let body = hcl::parse(content)?;
+        let dependency = dependency.to_lowercase();
+
+        let mut versions = BTreeSet::new();
+        for block in body
+            .blocks()
+            .filter(|block| block.identifier() == "provider")
+        {
+            let Some(address) = block.labels().first() else {
+                continue;
+            };
+
+            // provider addresses are case insensitive
+            let address = address.as_str().to_lowercase();
+            if address != dependency && !address.ends_with(&format!("/{dependency}")) {
+                continue;
+            }
+
+            let attribute = |key: &str| {
+                block
+                    .body()
+                    .attributes()
+                    .find(|attribute| attribute.key() == key)
+                    .and_then(|attribute| match attribute.expr() {
+                        hcl::Expression::String(value) => Some(value.as_str()),
+                        _ => None,
+                    })
+            };
+
+            let Some(version) = attribute("version") else {
+                continue;
+            };
+            versions.insert(match attribute("constraints") {
+                Some(constraints) => Version::new(version).with_constraints(constraints),
+                None => Version::new(version),
+            });
+        }
+
+        Ok(versions)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::describe_transition;
+
+        fn lock(version: &str, constraints: &str) -> String {
+            format!(
+                r#"provider "registry.terraform.io/hashicorp/aws" {{
+  version     = "{version}"
+  constraints = "{constraints}"
+  hashes = ["h1:abc="]
+}}
+"#
+            )
+        }
+
+        #[test]
+        fn constraints_only_reported_with_a_new_version() {
+            let versions = |version, constraints| {
+                get_dependency_version(&lock(version, constraints), "hashicorp/aws").unwrap()
+            };
+            let first = versions("5.31.0", "~> 5.0");
+            let relaxed = versions("5.31.0", ">= 5.0");
+            let upgraded = versions("5.32.0", ">= 5.0, < 6.0");
+
+            assert_eq!(first, relaxed);
+            assert_eq!(
+                describe_transition("hashicorp/aws", &relaxed, &upgraded).as_deref(),
+                Some("constraints changed from >= 5.0 to >= 5.0, < 6.0")
+            );
+        }
+    }
+}
+
+mod deno {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::{eyre, Result};
+    use serde_json::Value;
+
+    use super::{split_package_spec, Version};
+
+    /// Parses `deno.lock`. The dependency can be `npm:chalk`, `jsr:@std/path`, a bare name to look in
+    /// both registries, or the start of a url, eg: `https://deno.land/std`, to track remote imports.
+    ///
+    /// Where packages are depends on the schema version:
+    ///
+    /// - v2: `npm.packages`
+    /// - v3: `packages.npm` and `packages.jsr`
+    /// - v4 and later: `npm` and `jsr`
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: Value = serde_json::from_str(content)?;
+
+        if dependency.starts_with("http://") || dependency.starts_with("https://") {
+            return Ok(remote_versions(&lock, dependency));
+        }
+
+        let schema_version = lock
+            .get("version")
+            .and_then(Value::as_str)
+            .ok_or_else(|| eyre!("deno.lock has no version"))?;
+
+        let (registries, name) = match dependency.split_once(':') {
+            Some((registry @ ("npm" | "jsr"), name)) => (vec![registry], name),
+            _ => (vec!["npm", "jsr"], dependency),
+        };
+
+        let mut versions = BTreeSet::new();
+        for registry in registries {
+            let packages = match schema_version {
+                "2" => lock.get(registry).and_then(|npm| npm.get("packages")),
+                "3" => lock
+                    .get("packages")
+                    .and_then(|packages| packages.get(registry)),
+                _ => lock.get(registry),
+            };
+            let Some(packages) = packages.and_then(Value::as_object) else {
+                continue;
+            };
+
+            for key in packages.keys() {
+                let Some((package, version)) = split_package_spec(key) else {
+                    continue;
+                };
+                // npm packages can have peer dependencies after an `_`, eg: `foo@1.0.0_react@18.2.0`
+                let version = version.split('_').next().unwrap_or(version);
+
+                if package == name {
+                    versions.insert(Version::with_detail(version, registry));
+                }
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Remote imports are matched by url prefix, and their version is the part of the url right after it,
+    /// eg: `0.200.0` for `https://deno.land/std@0.200.0/path/mod.ts` with the prefix `https://deno.land/std`
+    fn remote_versions(lock: &Value, prefix: &str) -> BTreeSet<Version> {
+        let Some(remote) = lock.get("remote").and_then(Value::as_object) else {
+            return BTreeSet::new();
+        };
+
+        remote
+            .keys()
+            .filter_map(|url| url.strip_prefix(prefix))
+            .filter(|rest| rest.starts_with(['@', '/']))
+            .filter_map(|rest| rest[1..].split(['/', '?']).next())
+            .filter(|version| !version.is_empty())
+            .map(Version::new)
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn versions(content: &str, dependency: &str) -> Vec<String> {
+            get_dependency_version(content, dependency)
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        }
+
+        #[test]
+        fn npm_names_with_underscores() {
+            let v3 = r#"{
+                "version": "3",
+                "packages": {
+                    "specifiers": { "npm:string_decoder@1": "npm:string_decoder@1.3.0" },
+                    "npm": {
+                        "string_decoder@1.3.0": { "integrity": "x", "dependencies": {} },
+                        "react-dom@18.2.0_react@18.2.0": { "integrity": "x", "dependencies": {} }
+                    }
+                }
+            }"#;
+            let v4 = r#"{
+                "version": "4",
+                "specifiers": { "npm:string_decoder@1": "1.3.0" },
+                "npm": {
+                    "string_decoder@1.3.0": { "integrity": "x" },
+                    "react-dom@18.2.0_react@18.2.0": { "integrity": "x" }
+                }
+            }"#;
+
+            for lock in [v3, v4] {
+                assert_eq!(versions(lock, "string_decoder"), ["1.3.0 (npm)"]);
+                assert_eq!(versions(lock, "npm:string_decoder"), ["1.3.0 (npm)"]);
+                // the peer dependency suffix is still dropped from the version
+                assert_eq!(versions(lock, "react-dom"), ["18.2.0 (npm)"]);
+            }
+        }
+    }
+}
+
+mod helm {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize)]
+    struct ChartLock {
+        #[serde(default)]
+        dependencies: Vec<ChartDependency>,
+    }
+    #[derive(serde::Deserialize)]
+    struct ChartDependency {
+        /// Chart name
+        name: String,
+        /// Repository url, or an alias like `@bitnami`
+        #[serde(default)]
+        repository: String,
+        /// Chart version
+        version: String,
+    }
+
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: ChartLock = serde_yaml::from_str(content)?;
+
+        let versions = lock
+            .dependencies
+            .iter()
+            .filter(|chart| chart.name == dependency)
+            .map(|chart| match chart.repository.as_str() {
+                "" => Version::new(&chart.version),
+                repository => Version::with_detail(&chart.version, repository),
+            })
+            .collect();
+
+        Ok(versions)
+    }
+
+    /// Whether a line sets a container image, eg: `image: nginx:1.25` or `- image: "nginx:1.25"`
+    pub fn is_image_line(line: &str) -> bool {
+        image_reference(line).is_some()
+    }
+
+    /// Finds the tags of an image in Kubernetes manifests, docker compose files, or any other YAML
+    /// that sets `image: repo/name:tag`. Lines are scanned rather than parsed, so Helm templates work
+    /// too, as long as the image itself isn't templated
+    pub fn get_image_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let dependency = normalize_repository(dependency);
+
+        let versions = content
+            .lines()
+            .filter_map(image_reference)
+            .filter_map(|reference| {
+                let (repository, tag, digest) = split_reference(reference);
+                if normalize_repository(repository) != dependency {
+                    return None;
+                }
+
+                // an image pinned by digest alone has no tag to show
+                Some(match (tag, digest) {
+                    (Some(tag), Some(digest)) => Version::with_detail(tag, short_digest(digest)),
+                    (Some(tag), None) => Version::new(tag),
+                    (None, Some(digest)) => Version::new(short_digest(digest)),
+                    (None, None) => Version::new("latest"),
+                })
+            })
+            .collect();
+
+        Ok(versions)
+    }
+
+    fn image_reference(line: &str) -> Option<&str> {
+        let value = line
+            .trim_start()
+            .trim_start_matches("- ")
+            .trim_start()
+            .strip_prefix("image:")?;
+        let value = value.split(" #").next().unwrap_or(value).trim();
+        let value = value.trim_matches(|c| c == '"' || c == '\'');
+
+        if value.is_empty() || value.contains("{{") || value.contains(char::is_whitespace) {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Splits `registry:5000/repo/name:tag@sha256:...` into its repository, tag and digest
+    fn split_reference(reference: &str) -> (&str, Option<&str>, Option<&str>) {
+        let (name, digest) = match reference.split_once('@') {
+            Some((name, digest)) => (name, Some(digest)),
+            None => (reference, None),
+        };
+
+        // a registry can have a port, so the tag is only after the last `/`
+        let last_slash = name.rfind('/').map_or(0, |index| index + 1);
+        match name[last_slash..].split_once(':') {
+            Some((_, tag)) => (&name[..name.len() - tag.len() - 1], Some(tag), digest),
+            None => (name, None, digest),
+        }
+    }
+
+    /// Docker Hub images can be written several ways, eg: `nginx`, `library/nginx` and
+    /// `docker.io/library/nginx` are the same image
+    fn normalize_repository(repository: &str) -> &str {
+        let repository = repository
+            .strip_prefix("docker.io/")
+            .or_else(|| repository.strip_prefix("index.docker.io/"))
+            .unwrap_or(repository);
+
+        repository.strip_prefix("library/").unwrap_or(repository)
+    }
+
+    fn short_digest(digest: &str) -> String {
+        format!("{:.19}", digest)
+    }
+}
+
+mod julia {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    struct JuliaPackage {
+        /// Missing for standard libraries and packages tracking a path
+        version: Option<String>,
+        #[serde(rename = "git-tree-sha1")]
+        git_tree_sha1: Option<String>,
+        #[serde(rename = "repo-url")]
+        repo_url: Option<String>,
+        /// Set for packages added with `Pkg.develop`
+        path: Option<String>,
+    }
+
+    /// Parses `Manifest.toml`. Format v1 lists packages as top level `[[Name]]` tables, while v2
+    /// moves them under `[[deps.Name]]`. There can be several packages with the same name, with
+    /// different UUIDs
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let manifest: toml::Table = toml::from_str(content)?;
+
+        let packages = if manifest.contains_key("manifest_format") {
+            manifest.get("deps").and_then(|deps| deps.get(dependency))
+        } else {
+            manifest.get(dependency)
+        };
+        let Some(packages) = packages else {
+            return Ok(BTreeSet::new());
+        };
+        let packages: Vec<JuliaPackage> = packages.clone().try_into()?;
+
+        let versions = packages
+            .iter()
+            .filter_map(|package| {
+                // standard libraries only have a version since Julia 1.8 or so, fall back to the tree hash
+                let version = match (&package.version, &package.git_tree_sha1) {
+                    (Some(version), _) => version.clone(),
+                    (None, Some(tree)) => format!("{:.7}", tree),
+                    (None, None) => return None,
+                };
+
+                Some(match package.repo_url.as_ref().or(package.path.as_ref()) {
+                    Some(source) => Version::with_detail(version, source),
+                    None => Version::new(version),
+                })
+            })
+            .collect();
+
+        Ok(versions)
+    }
+}
+
+mod renv {
+    use std::collections::{BTreeSet, HashMap};
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(rename_all = "PascalCase")]
+    struct RenvLock {
+        #[serde(default)]
+        packages: HashMap<String, RenvPackage>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(rename_all = "PascalCase")]
+    struct RenvPackage {
+        version: String,
+        /// Where the package was installed from, eg: `Repository`, `GitHub` or `Bioconductor`
+        source: Option<String>,
+        /// Name of the repository, eg: `CRAN`, when `source` is `Repository`
+        repository: Option<String>,
+    }
+
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: RenvLock = serde_json::from_str(content)?;
+
+        let versions = lock
+            .packages
+            .get(dependency)
+            .map(
+                |package| match package.repository.as_ref().or(package.source.as_ref()) {
+                    Some(source) => Version::with_detail(&package.version, source),
+                    None => Version::new(&package.version),
+                },
+            )
+            .into_iter()
+            .collect();
+
+        Ok(versions)
+    }
+}
+
+mod haskell {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+    use serde_yaml::Value;
+
+    use super::Version;
+
+    /// Parses `cabal.project.freeze`, whose `constraints` field pins every package, eg:
+    ///
+    /// ```text
+    /// constraints: any.aeson ==2.0.3.0,
+    ///              aeson -cffi +ordered-keymap,
+    ///              any.base ==4.16.4.0 installed,
+    /// ```
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let mut versions = BTreeSet::new();
+
+        let mut in_constraints = false;
+        for line in content.lines() {
+            let constraints = if let Some(rest) = line.strip_prefix("constraints:") {
+                in_constraints = true;
+                rest
+            } else if in_constraints && line.starts_with(char::is_whitespace) {
+                line
+            } else {
+                in_constraints = false;
+                continue;
+            };
+
+            for constraint in constraints.split(',') {
+                let mut words = constraint.split_whitespace();
+                let Some(package) = words.next() else {
+                    continue;
+                };
+                // flag constraints don't have a version
+                let Some(version) = words.next().and_then(|word| word.strip_prefix("==")) else {
+                    continue;
+                };
+
+                // the qualifier says which part of the build plan the package is in, eg: `setup.Cabal`
+                let (qualifier, name) = package.split_once('.').unwrap_or(("any", package));
+                if name != dependency {
+                    continue;
+                }
+
+                let installed = words.next() == Some("installed");
+                versions.insert(match (qualifier, installed) {
+                    ("any", false) => Version::new(version),
+                    ("any", true) => Version::with_detail(version, "installed"),
+                    (qualifier, false) => Version::with_detail(version, qualifier),
+                    (qualifier, true) => {
+                        Version::with_detail(version, format!("{qualifier}, installed"))
+                    }
+                });
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Parses `stack.yaml.lock`, which only lists the `extra-deps` that aren't in the snapshot.
+    /// Hackage packages look like `aeson-2.0.3.0@sha256:...,1234`, git ones are shown as their commit
+    pub fn get_stack_dependency_version(
+        content: &str,
+        dependency: &str,
+    ) -> Result<BTreeSet<Version>> {
+        let lock: Value = serde_yaml::from_str(content)?;
+
+        let packages = lock.get("packages").and_then(Value::as_sequence);
+        let completed = packages
+            .into_iter()
+            .flatten()
+            .filter_map(|package| package.get("completed"));
+
+        let mut versions = BTreeSet::new();
+        for package in completed {
+            if let Some(hackage) = package.get("hackage").and_then(Value::as_str) {
+                let package_id = hackage.split('@').next().unwrap_or(hackage);
+                if let Some((name, version)) = package_id.rsplit_once('-') {
+                    if name == dependency {
+                        versions.insert(Version::new(version));
+                    }
+                }
+            } else if package.get("name").and_then(Value::as_str) == Some(dependency) {
+                let commit = package.get("commit").and_then(Value::as_str);
+                let repository = package.get("git").or_else(|| package.get("url"));
+                if let (Some(commit), Some(repository)) =
+                    (commit, repository.and_then(Value::as_str))
+                {
+                    versions.insert(Version::with_detail(format!("{:.7}", commit), repository));
+                }
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+mod conan {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    struct ConanLock {
+        #[serde(default)]
+        requires: Vec<String>,
+        #[serde(default)]
+        build_requires: Vec<String>,
+        #[serde(default)]
+        python_requires: Vec<String>,
+    }
+
+    /// Parses Conan 2's `conan.lock`, where references look like `zlib/1.3#<revision>%<timestamp>`,
+    /// optionally with a `@user/channel`. The recipe revision is kept apart from the version, so a
+    /// new revision of the same version shows up in the timeline
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let lock: ConanLock = serde_json::from_str(content)?;
+
+        let requires = lock.requires.iter().map(|reference| (reference, None));
+        let build = lock
+            .build_requires
+            .iter()
+            .map(|reference| (reference, Some("build")));
+        let python = lock
+            .python_requires
+            .iter()
+            .map(|reference| (reference, Some("python")));
+
+        let versions = requires
+            .chain(build)
+            .chain(python)
+            .filter_map(|(reference, kind)| {
+                let (reference, revision) = match reference.split_once('#') {
+                    Some((reference, revision)) => (reference, revision.split('%').next()),
+                    None => (reference.as_str(), None),
+                };
+                let (reference, user_channel) = match reference.split_once('@') {
+                    Some((reference, user_channel)) => (reference, Some(user_channel)),
+                    None => (reference, None),
+                };
+
+                let (name, version) = reference.split_once('/')?;
+                if name != dependency {
+                    return None;
+                }
+
+                let detail = [
+                    kind.map(str::to_string),
+                    user_channel.map(|user_channel| format!("@{user_channel}")),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+
+                let version = if detail.is_empty() {
+                    Version::new(version)
+                } else {
+                    Version::with_detail(version, detail.join(", "))
+                };
+
+                Some(match revision {
+                    Some(revision) => version.with_revision(format!("{:.7}", revision)),
+                    None => version,
+                })
+            })
+            .collect();
+
+        Ok(versions)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::describe_transition;
+
+        fn lock(reference: &str) -> String {
+            format!(r#"{{"version": "0.5", "requires": ["{reference}"], "build_requires": []}}"#)
+        }
+
+        #[test]
+        fn revision_only_change() {
+            let previous =
+                get_dependency_version(&lock("zlib/1.3#b3b71bfe8dd0%1695115024.012"), "zlib")
+                    .unwrap();
+            let current =
+                get_dependency_version(&lock("zlib/1.3#c0ffee1a2b3c%1700000000.0"), "zlib")
+                    .unwrap();
+
+            assert_ne!(previous, current);
+            assert_eq!(
+                describe_transition("zlib", &previous, &current).as_deref(),
+                Some("new recipe revision of zlib 1.3: b3b71bf -> c0ffee1")
+            );
+        }
+    }
+}
+
+mod vcpkg {
+    use std::collections::BTreeSet;
+
+    use color_eyre::eyre::Result;
+
+    use super::Version;
+
+    #[derive(serde::Deserialize, Debug)]
+    struct VcpkgManifest {
+        #[serde(rename = "builtin-baseline")]
+        builtin_baseline: Option<String>,
+        #[serde(default)]
+        dependencies: Vec<VcpkgDependency>,
+        #[serde(default)]
+        overrides: Vec<VcpkgOverride>,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(untagged)]
+    enum VcpkgDependency {
+        Name(String),
+        Detailed {
+            name: String,
+            #[serde(rename = "version>=")]
+            minimum_version: Option<String>,
+        },
+    }
+    #[derive(serde::Deserialize, Debug)]
+    struct VcpkgOverride {
+        name: String,
+        /// Older manifests use `version-string`, `version-semver` or `version-date` instead
+        #[serde(
+            alias = "version-string",
+            alias = "version-semver",
+            alias = "version-date"
+        )]
+        version: String,
+        #[serde(rename = "port-version")]
+        port_version: Option<u32>,
+    }
+
+    /// Parses `vcpkg.json`. It doesn't lock versions itself, so a port is shown as the version it's
+    /// overridden to, or otherwise as the `builtin-baseline` commit, which decides which version vcpkg
+    /// picks. Use `builtin-baseline` as the dependency to follow the baseline on its own
+    pub fn get_dependency_version(content: &str, dependency: &str) -> Result<BTreeSet<Version>> {
+        let manifest: VcpkgManifest = serde_json::from_str(content)?;
+        let baseline = manifest
+            .builtin_baseline
+            .as_ref()
+            .map(|baseline| format!("{:.7}", baseline));
+
+        let mut versions = BTreeSet::new();
+        if dependency == "builtin-baseline" {
+            versions.extend(baseline.map(Version::new));
+            return Ok(versions);
+        }
+
+        if let Some(pin) = manifest.overrides.iter().find(|pin| pin.name == dependency) {
+            let version = match pin.port_version {
+                Some(port_version) if port_version > 0 => {
+                    format!("{}#{}", pin.version, port_version)
+                }
+                _ => pin.version.clone(),
+            };
+            versions.insert(Version::with_detail(version, "override"));
+            return Ok(versions);
+        }
+
+        let minimum_version = manifest.dependencies.iter().find_map(|dep| match dep {
+            VcpkgDependency::Name(name) if name == dependency => Some(None),
+            VcpkgDependency::Detailed {
+                name,
+                minimum_version,
+            } if name == dependency => Some(minimum_version.as_ref()),
+            _ => None,
+        });
+        let Some(minimum_version) = minimum_version else {
+            return Ok(versions);
+        };
+
+        versions.extend(match (baseline, minimum_version) {
+            (Some(baseline), Some(minimum)) => Some(Version::with_detail(
+                baseline,
+                format!("baseline, >= {minimum}"),
+            )),
+            (Some(baseline), None) => Some(Version::with_detail(baseline, "baseline")),
+            (None, Some(minimum)) => Some(Version::new(format!(">= {minimum}"))),
+            (None, None) => None,
+        });
+
+        Ok(versions)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::PackageManager;
+
+        #[test]
+        fn sniffed_despite_version_and_dependencies() {
+            let manifest = br#"{
+                "name": "app",
+                "version": "1.0.0",
+                "dependencies": ["zlib"],
+                "builtin-baseline": "3426db05b996481ca31e95fff3734cf23e0f51bc"
+            }"#;
+
+            assert!(matches!(
+                PackageManager::guess_from_content(manifest),
+                Some(PackageManager::Vcpkg)
+            ));
         }
     }
 }